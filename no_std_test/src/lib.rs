@@ -0,0 +1,30 @@
+//! Checks that the tree, its nodes and its iterators may be used with only `alloc`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use lz_eytzinger_tree::{traversal::DepthFirstOrder, EytzingerTree};
+
+pub fn depth_first_values() -> Vec<u32> {
+    let mut tree = EytzingerTree::new(2);
+    {
+        let mut root = tree.set_root_value(5);
+        root.set_child_value(0, 2);
+        root.set_child_value(1, 7);
+    }
+
+    let borrowed: Vec<_> = tree
+        .depth_first_iter(DepthFirstOrder::PreOrder)
+        .map(|node| *node.value())
+        .collect();
+
+    let owned: Vec<_> = tree
+        .into_breadth_first_iterator()
+        .zip(borrowed)
+        .map(|(owned, borrowed)| owned + borrowed)
+        .collect();
+
+    owned
+}
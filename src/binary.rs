@@ -0,0 +1,178 @@
+use crate::EytzingerTree;
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Write},
+};
+
+/// A value which may be written to and read from the binary format of `EytzingerTree::write_to`.
+/// Values are written as their little-endian bytes.
+pub trait BinaryValue: Sized {
+    /// Writes the value to the writer.
+    fn write_bytes<W: Write>(&self, w: &mut W) -> io::Result<()>;
+
+    /// Reads a value from the reader.
+    fn read_bytes<R: Read>(r: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! impl_binary_value {
+    ($($ty:ty),*) => {
+        $(
+            impl BinaryValue for $ty {
+                fn write_bytes<W: Write>(&self, w: &mut W) -> io::Result<()> {
+                    w.write_all(&self.to_le_bytes())
+                }
+
+                fn read_bytes<R: Read>(r: &mut R) -> io::Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$ty>()];
+                    r.read_exact(&mut bytes)?;
+                    Ok(<$ty>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_value!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl BinaryValue for bool {
+    fn write_bytes<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        u8::from(*self).write_bytes(w)
+    }
+
+    fn read_bytes<R: Read>(r: &mut R) -> io::Result<Self> {
+        match u8::read_bytes(r)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("a bool should be 0 or 1")),
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_usize<R: Read>(r: &mut R) -> io::Result<usize> {
+    usize::try_from(u64::read_bytes(r)?).map_err(|_| invalid_data("a value exceeds usize::MAX"))
+}
+
+impl<N: BinaryValue> EytzingerTree<N> {
+    /// Writes the tree in a simple binary format, this is the maximum number of children per node
+    /// and the number of nodes followed by the flat storage index and value of each node in
+    /// breadth-first order. Numbers are written as little-endian `u64`s. `read_from` may be used to
+    /// read the tree again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree was created with `mixed_arity`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `w`.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        assert!(
+            self.index_calculator.is_uniform(),
+            "operation requires the same arity for every level"
+        );
+
+        (self.max_children_per_node() as u64).write_bytes(w)?;
+        (self.len as u64).write_bytes(w)?;
+
+        for (index, value) in self.enumerate_values() {
+            (index as u64).write_bytes(w)?;
+            value.write_bytes(w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a tree written by `write_to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from reading from `r`, or an error of kind `InvalidData` if the arity is
+    /// 0, the flat storage indexes are not ascending, there is no node for the parent of a node or
+    /// the storage for an index could not be allocated.
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+        let arity = read_usize(r)?;
+        if arity == 0 {
+            return Err(invalid_data("the arity should be greater than 0"));
+        }
+        let len = read_usize(r)?;
+
+        let mut tree = EytzingerTree::new(arity);
+        let mut previous_index = None;
+        for _ in 0..len {
+            let index = read_usize(r)?;
+            match previous_index {
+                Some(previous_index) if index <= previous_index => {
+                    return Err(invalid_data("the indexes should be ascending"));
+                }
+                _ => {}
+            }
+
+            let parent_occupied = tree
+                .parent_index(index)
+                .map_or(index == 0, |parent_index| tree.node(parent_index).is_some());
+            if !parent_occupied {
+                return Err(invalid_data(
+                    "there should be a node for the parent of each node",
+                ));
+            }
+
+            let value = N::read_bytes(r)?;
+            tree.try_set_value(index, value)
+                .map_err(|_| invalid_data("the tree is too large to allocate"))?;
+            previous_index = Some(index);
+        }
+
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::sample_tree, EytzingerTree};
+    use std::io::{Cursor, ErrorKind};
+
+    #[test]
+    fn write_to_read_from() {
+        let tree = sample_tree();
+
+        let mut buffer = vec![];
+        tree.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 16 + 7 * (8 + 4));
+
+        let read = EytzingerTree::<u32>::read_from(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(read, tree);
+    }
+
+    #[test]
+    fn write_to_read_from_empty() {
+        let tree = EytzingerTree::<bool>::new(3);
+
+        let mut buffer = vec![];
+        tree.write_to(&mut buffer).unwrap();
+
+        let read = EytzingerTree::<bool>::read_from(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(read, tree);
+    }
+
+    #[test]
+    fn read_from_invalid_data() {
+        let read = |words: &[u64]| {
+            let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+            EytzingerTree::<u64>::read_from(&mut Cursor::new(bytes)).map_err(|e| e.kind())
+        };
+
+        assert_eq!(read(&[0, 0]), Err(ErrorKind::InvalidData));
+        assert_eq!(read(&[2, 2, 0, 5, 0, 6]), Err(ErrorKind::InvalidData));
+        assert_eq!(read(&[2, 2, 0, 5, 3, 6]), Err(ErrorKind::InvalidData));
+        assert_eq!(read(&[2, 1, 1, 5]), Err(ErrorKind::InvalidData));
+        assert_eq!(read(&[2, 2, 0, 5]), Err(ErrorKind::UnexpectedEof));
+        assert_eq!(
+            read(&[1 << 62, 3, 0, 5, 1, 6, (1 << 62) + 1, 7]),
+            Err(ErrorKind::InvalidData)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::EytzingerTree;
+
+/// Builds an Eytzinger tree from values supplied in depth-first order, such as by a recursive
+/// descent parser, without needing the path of each node.
+///
+/// The builder has a current position which starts at the root. `push_value` sets the value at
+/// the current position and moves to the first child of the new node, `open_child` moves to
+/// another child of that node and `close` moves back up to the new node's position.
+///
+/// # Examples
+///
+/// ```
+/// use lz_eytzinger_tree::TreeBuilder;
+///
+/// let mut builder = TreeBuilder::new(2);
+/// builder.push_value(5);
+/// builder.push_value(2);
+/// builder.close();
+/// builder.open_child(1);
+/// builder.push_value(7);
+///
+/// let tree = builder.finish();
+/// let values: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+/// assert_eq!(values, vec![5, 2, 7]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TreeBuilder<N> {
+    tree: EytzingerTree<N>,
+    parent: Option<usize>,
+    /// The index of the current position, `None` for the first child of `parent`. This is only
+    /// calculated once needed as the children of a leaf may not be addressable.
+    index: Option<usize>,
+}
+
+impl<N> TreeBuilder<N> {
+    /// Creates a new builder for a tree with the specified maximum number of child nodes per
+    /// parent.
+    pub fn new(max_children_per_node: usize) -> Self {
+        Self {
+            tree: EytzingerTree::new(max_children_per_node),
+            parent: None,
+            index: Some(0),
+        }
+    }
+
+    /// Sets the value at the current position, replacing any existing value, then moves to the
+    /// first child of the node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current position is a child which can not be addressed.
+    pub fn push_value(&mut self, value: N) {
+        let index = match (self.index, self.parent) {
+            (Some(index), _) => index,
+            (None, Some(parent)) => self.tree.child_index(parent, 0),
+            (None, None) => unreachable!("the root position should always be known"),
+        };
+
+        self.tree.set_value(index, value);
+
+        self.parent = Some(index);
+        self.index = None;
+    }
+
+    /// Moves to the child at the specified index of the last node whose children were entered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value has been pushed, `index` is not less than `max_children_per_node` or the
+    /// child can not be addressed.
+    pub fn open_child(&mut self, index: usize) {
+        let parent = self
+            .parent
+            .expect("a value should be pushed before opening a child");
+
+        self.index = Some(self.tree.child_index(parent, index));
+    }
+
+    /// Moves back up to the position of the last node whose children were entered, a child of
+    /// its parent may then be opened.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value has been pushed.
+    pub fn close(&mut self) {
+        let parent = self
+            .parent
+            .expect("a value should be pushed before closing");
+
+        self.index = Some(parent);
+        self.parent = self.tree.parent_index(parent);
+    }
+
+    /// Gets the tree which has been built.
+    pub fn finish(self) -> EytzingerTree<N> {
+        self.tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TreeBuilder;
+    use crate::test_util::sample_tree;
+
+    #[test]
+    fn builds_sample_tree() {
+        let mut builder = TreeBuilder::new(2);
+        builder.push_value(5);
+        builder.push_value(2);
+        builder.push_value(1);
+        builder.close();
+        builder.open_child(1);
+        builder.push_value(4);
+        builder.push_value(3);
+        builder.close();
+        builder.close();
+        builder.close();
+        builder.open_child(1);
+        builder.push_value(7);
+        builder.open_child(1);
+        builder.push_value(8);
+
+        assert_eq!(builder.finish(), sample_tree());
+    }
+
+    #[test]
+    fn push_leaf_without_addressable_children() {
+        // the children of the node at index 2 would be beyond usize::MAX
+        let mut builder = TreeBuilder::new(usize::MAX / 2 + 1);
+        builder.push_value(5);
+        builder.push_value(2);
+        builder.close();
+        builder.open_child(1);
+        builder.push_value(7);
+
+        let tree = builder.finish();
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.get(1), Some(&2));
+        assert_eq!(tree.get(2), Some(&7));
+    }
+
+    #[test]
+    #[should_panic(expected = "a value should be pushed before closing")]
+    fn close_without_value_panics() {
+        TreeBuilder::<u32>::new(2).close();
+    }
+}
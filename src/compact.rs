@@ -0,0 +1,177 @@
+use crate::{error::CompactTreeError, EytzingerTree};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A compact representation of an Eytzinger tree for serialization, this only stores the occupied
+/// slots of the tree so it suits trees with a low `density`.
+///
+/// Each entry is the value of a node along with the difference between its index in the
+/// underlying storage and that of the previous entry, or its index for the first entry. The
+/// entries are in breadth-first order. This is serialized as the tuple `(arity, entries)`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CompactTree<N> {
+    /// The maximum number of children per node.
+    pub arity: usize,
+    /// The index deltas and values of the nodes.
+    pub entries: Vec<(u64, N)>,
+}
+
+impl<N: Serialize> Serialize for CompactTree<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.arity, &self.entries).serialize(serializer)
+    }
+}
+
+impl<'de, N: Deserialize<'de>> Deserialize<'de> for CompactTree<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (arity, entries) = Deserialize::deserialize(deserializer)?;
+        Ok(Self { arity, entries })
+    }
+}
+
+impl<N> EytzingerTree<N> {
+    /// Converts the tree into a compact representation which only includes the occupied slots,
+    /// `from_compact` may be used to build the tree again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree was created with `mixed_arity`.
+    pub fn to_compact(&self) -> CompactTree<N>
+    where
+        N: Clone,
+    {
+        assert!(
+            self.index_calculator.is_uniform(),
+            "operation requires the same arity for every level"
+        );
+
+        let mut previous_index = 0;
+        let entries = self
+            .enumerate_values()
+            .map(|(index, value)| {
+                let delta = (index - previous_index) as u64;
+                previous_index = index;
+                (delta, value.clone())
+            })
+            .collect();
+
+        CompactTree {
+            arity: self.max_children_per_node(),
+            entries,
+        }
+    }
+
+    /// Builds a tree from the compact representation produced by `to_compact`.
+    ///
+    /// # Errors
+    ///
+    /// * `CompactTreeError::InvalidArity` if the arity is 0.
+    /// * `CompactTreeError::InvalidIndex` if an index can not be represented by a `usize` or is
+    ///   not after the previous index.
+    /// * `CompactTreeError::MissingParent` if there is no node for the parent of an entry.
+    /// * `CompactTreeError::TooLarge` if the storage for an index could not be allocated, as the
+    ///   storage is dense this may happen for a small number of entries with large indexes.
+    pub fn from_compact(compact: CompactTree<N>) -> Result<Self, CompactTreeError> {
+        if compact.arity == 0 {
+            return Err(CompactTreeError::InvalidArity);
+        }
+
+        let mut tree = EytzingerTree::new(compact.arity);
+
+        let mut index: Option<usize> = None;
+        for (delta, value) in compact.entries {
+            let next_index = usize::try_from(delta)
+                .ok()
+                .and_then(|delta| match index {
+                    Some(index) if delta > 0 => index.checked_add(delta),
+                    Some(_) => None,
+                    None => Some(delta),
+                })
+                .ok_or(CompactTreeError::InvalidIndex)?;
+
+            if let Some(parent_index) = tree.parent_index(next_index) {
+                if tree.node(parent_index).is_none() {
+                    return Err(CompactTreeError::MissingParent {
+                        path: tree.index_calculator.path(next_index),
+                    });
+                }
+            }
+
+            tree.try_set_value(next_index, value)
+                .map_err(|_| CompactTreeError::TooLarge)?;
+            index = Some(next_index);
+        }
+
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompactTree;
+    use crate::{error::CompactTreeError, test_util::sample_tree, EytzingerTree};
+
+    #[test]
+    fn to_compact() {
+        let compact = sample_tree().to_compact();
+
+        assert_eq!(compact.arity, 2);
+        assert_eq!(
+            compact.entries,
+            vec![(0, 5), (1, 2), (1, 7), (1, 1), (1, 4), (2, 8), (3, 3)]
+        );
+        assert_eq!(EytzingerTree::from_compact(compact), Ok(sample_tree()));
+    }
+
+    #[test]
+    fn to_compact_sparse_chain() {
+        // each level of a chain doubles the slots needed by a dense tree so this is kept shallow
+        let mut tree = EytzingerTree::new(2);
+        {
+            let mut node = tree.set_root_value(0);
+            for value in 1..=16 {
+                node = node.to_child_entry(0).or_insert(value);
+            }
+        }
+
+        let compact = tree.to_compact();
+        assert_eq!(compact.entries.len(), 17);
+
+        let json = serde_json::to_string(&compact).unwrap();
+        let compact: CompactTree<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(EytzingerTree::from_compact(compact), Ok(tree));
+    }
+
+    #[test]
+    fn from_compact_errors() {
+        let compact = |arity, entries| CompactTree { arity, entries };
+
+        assert_eq!(
+            EytzingerTree::from_compact(compact(0, vec![(0, 1)])),
+            Err(CompactTreeError::InvalidArity)
+        );
+        assert_eq!(
+            EytzingerTree::from_compact(compact(2, vec![(0, 1), (0, 2)])),
+            Err(CompactTreeError::InvalidIndex)
+        );
+        assert_eq!(
+            EytzingerTree::from_compact(compact(2, vec![(0, 1), (3, 2)])),
+            Err(CompactTreeError::MissingParent { path: vec![0, 0] })
+        );
+        assert_eq!(
+            EytzingerTree::from_compact(compact(2, vec![(1, 1)])),
+            Err(CompactTreeError::MissingParent { path: vec![0] })
+        );
+        assert_eq!(
+            EytzingerTree::from_compact(compact(1 << 62, vec![(0, 1), (1, 2), (1 << 62, 3)])),
+            Err(CompactTreeError::TooLarge)
+        );
+    }
+}
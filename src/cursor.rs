@@ -0,0 +1,123 @@
+use crate::{EytzingerTree, Node};
+
+/// A read-only cursor which may be moved around an Eytzinger tree. The cursor starts at the root.
+///
+/// Each move returns whether it succeeded, the cursor is left where it was if a move fails.
+#[derive(Debug)]
+pub struct Cursor<'a, N>
+where
+    N: 'a,
+{
+    tree: &'a EytzingerTree<N>,
+    index: usize,
+}
+
+impl<'a, N> Clone for Cursor<'a, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, N> Copy for Cursor<'a, N> {}
+
+impl<'a, N> Cursor<'a, N> {
+    pub(crate) fn new(tree: &'a EytzingerTree<N>) -> Self {
+        Self { tree, index: 0 }
+    }
+
+    /// Gets the Eytzinger tree this cursor is for.
+    pub fn tree(&self) -> &'a EytzingerTree<N> {
+        self.tree
+    }
+
+    /// Gets the node the cursor is at, `None` if the tree is empty.
+    pub fn node(&self) -> Option<Node<'a, N>> {
+        self.tree.node(self.index)
+    }
+
+    /// Gets the value of the node the cursor is at, `None` if the tree is empty.
+    pub fn value(&self) -> Option<&'a N> {
+        self.node().map(|node| node.value())
+    }
+
+    /// Moves the cursor to the child at the specified index.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a child to move to, `false` otherwise.
+    pub fn move_to_child(&mut self, index: usize) -> bool {
+        if index >= self.tree.child_count(self.index) {
+            return false;
+        }
+
+        let child_index = self.tree.child_index(self.index, index);
+        self.move_to(child_index)
+    }
+
+    /// Moves the cursor to the parent of the current node.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a parent to move to, `false` otherwise.
+    pub fn move_to_parent(&mut self) -> bool {
+        match self.tree.parent_index(self.index) {
+            Some(parent_index) => self.move_to(parent_index),
+            None => false,
+        }
+    }
+
+    /// Moves the cursor to the root of the tree.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a root to move to, `false` otherwise.
+    pub fn move_to_root(&mut self) -> bool {
+        self.move_to(0)
+    }
+
+    fn move_to(&mut self, index: usize) -> bool {
+        if self.tree.node(index).is_some() {
+            self.index = index;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::sample_tree, EytzingerTree};
+
+    #[test]
+    fn moves_to_leaf() {
+        let tree = sample_tree();
+        let mut cursor = tree.cursor();
+
+        assert_eq!(cursor.value(), Some(&5));
+        assert!(cursor.move_to_child(1));
+        assert!(!cursor.move_to_child(0));
+        assert_eq!(cursor.value(), Some(&7));
+        assert!(cursor.move_to_parent());
+        assert!(!cursor.move_to_parent());
+        assert!(cursor.move_to_child(0));
+        assert!(cursor.move_to_child(1));
+        assert!(cursor.move_to_child(0));
+        assert!(!cursor.move_to_child(2));
+
+        assert_eq!(cursor.value(), Some(&3));
+
+        assert!(cursor.move_to_root());
+        assert_eq!(cursor.value(), Some(&5));
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = EytzingerTree::<u32>::new(2);
+        let mut cursor = tree.cursor();
+
+        assert_eq!(cursor.value(), None);
+        assert!(!cursor.move_to_root());
+        assert!(!cursor.move_to_child(0));
+    }
+}
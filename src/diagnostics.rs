@@ -0,0 +1,35 @@
+//! Macros for the internal diagnostic messages. Messages are routed through `tracing` when the
+//! `tracing` feature is enabled, otherwise through `log` when the `log` feature is enabled,
+//! otherwise they compile out.
+
+macro_rules! diagnostic {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        {
+            ::tracing::$level!($($arg)+);
+        }
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        {
+            ::log::$level!($($arg)+);
+        }
+        #[cfg(not(any(feature = "log", feature = "tracing")))]
+        {
+            // keep the arguments used so disabling diagnostics introduces no warnings
+            if false {
+                let _ = format_args!($($arg)+);
+            }
+        }
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        diagnostic!(debug, $($arg)+)
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        diagnostic!(trace, $($arg)+)
+    };
+}
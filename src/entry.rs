@@ -33,7 +33,7 @@ impl<'a, N> VacantEntry<'a, N> {
     }
 
     /// Gets the parent of this entry or `None` is there was none (i.e. if this entry is for the root).
-    pub fn parent(&self) -> Option<Node<N>> {
+    pub fn parent(&self) -> Option<Node<'_, N>> {
         self.tree.parent(self.index)
     }
 
@@ -77,7 +77,7 @@ impl<'a, N> Entry<'a, N> {
     }
 
     /// Gets the parent of this entry or `None` is there was none (i.e. if this entry is for the root).
-    pub fn parent(&self) -> Option<Node<N>> {
+    pub fn parent(&self) -> Option<Node<'_, N>> {
         match self {
             Entry::Occupied(node) => node.parent(),
             Entry::Vacant(vacant_entry) => vacant_entry.parent(),
@@ -160,7 +160,7 @@ impl<'a, N> Entry<'a, N> {
     /// # Returns
     ///
     /// The node if there was one, `None` otherwise.
-    pub fn node(&self) -> Option<Node<N>> {
+    pub fn node(&self) -> Option<Node<'_, N>> {
         match self {
             Entry::Occupied(node) => Some(node.as_node()),
             Entry::Vacant(_) => None,
@@ -181,7 +181,7 @@ impl<'a, N> Entry<'a, N> {
 
     /// Gets an iterator over the immediate children of this node. This only includes children
     /// for which there is a node.
-    pub fn child_iter(&self) -> EntryIter<NodeChildIter<N>> {
+    pub fn child_iter(&self) -> EntryIter<NodeChildIter<'_, N>> {
         match self {
             Entry::Occupied(node) => EntryIter::Occupied(node.child_iter()),
             Entry::Vacant(_) => EntryIter::Vacant,
@@ -189,7 +189,7 @@ impl<'a, N> Entry<'a, N> {
     }
 
     /// Gets a depth-first iterator over this and all child nodes.
-    pub fn depth_first_iter(&self, order: DepthFirstOrder) -> EntryIter<DepthFirstIter<N>> {
+    pub fn depth_first_iter(&self, order: DepthFirstOrder) -> EntryIter<DepthFirstIter<'_, N>> {
         match self {
             Entry::Occupied(node) => EntryIter::Occupied(node.depth_first_iter(order)),
             Entry::Vacant(_) => EntryIter::Vacant,
@@ -197,7 +197,7 @@ impl<'a, N> Entry<'a, N> {
     }
 
     /// Gets a breadth-first iterator over this and all child nodes.
-    pub fn breadth_first_iter(&self) -> EntryIter<BreadthFirstIter<N>> {
+    pub fn breadth_first_iter(&self) -> EntryIter<BreadthFirstIter<'_, N>> {
         match self {
             Entry::Occupied(node) => EntryIter::Occupied(node.breadth_first_iter()),
             Entry::Vacant(_) => EntryIter::Vacant,
@@ -205,6 +205,88 @@ impl<'a, N> Entry<'a, N> {
     }
 }
 
+/// A read-only entry which references a position in an Eytzinger tree. The position may or may not
+/// have a node.
+#[derive(Debug)]
+pub enum EntryRef<'a, N>
+where
+    N: 'a,
+{
+    /// When the entry references a node which exists with a value.
+    Occupied(Node<'a, N>),
+
+    /// When the entry references a non-existent node.
+    Vacant(VacantEntryRef<'a, N>),
+}
+
+impl<'a, N> Copy for EntryRef<'a, N> {}
+
+impl<'a, N> Clone for EntryRef<'a, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// For a read-only entry where the node does not exist.
+#[derive(Debug)]
+pub struct VacantEntryRef<'a, N>
+where
+    N: 'a,
+{
+    pub(crate) tree: &'a EytzingerTree<N>,
+    pub(crate) index: usize,
+}
+
+impl<'a, N> Copy for VacantEntryRef<'a, N> {}
+
+impl<'a, N> Clone for VacantEntryRef<'a, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, N> VacantEntryRef<'a, N> {
+    /// Gets the Eytzinger tree this entry is for.
+    pub fn tree(&self) -> &'a EytzingerTree<N> {
+        self.tree
+    }
+
+    /// Gets the parent of this entry or `None` is there was none (i.e. if this entry is for the root).
+    pub fn parent(&self) -> Option<Node<'a, N>> {
+        self.tree.parent(self.index)
+    }
+}
+
+impl<'a, N> EntryRef<'a, N> {
+    /// Gets the Eytzinger tree this entry is for.
+    pub fn tree(&self) -> &'a EytzingerTree<N> {
+        match self {
+            EntryRef::Occupied(node) => node.tree(),
+            EntryRef::Vacant(vacant_entry) => vacant_entry.tree(),
+        }
+    }
+
+    /// Gets the parent of this entry or `None` is there was none (i.e. if this entry is for the root).
+    pub fn parent(&self) -> Option<Node<'a, N>> {
+        match self {
+            EntryRef::Occupied(node) => node.parent(),
+            EntryRef::Vacant(vacant_entry) => vacant_entry.parent(),
+        }
+    }
+
+    /// Gets the node this entry is for, if there is one.
+    ///
+    /// # Returns
+    ///
+    /// The node if there was one, `None` otherwise.
+    pub fn node(&self) -> Option<Node<'a, N>> {
+        match self {
+            EntryRef::Occupied(node) => Some(*node),
+            EntryRef::Vacant(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum EntryIter<I> {
     Occupied(I),
@@ -224,3 +306,39 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::EytzingerTree;
+
+    #[test]
+    fn and_modify_or_insert_modifies_occupied() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            root.set_child_value(0, 2);
+        }
+
+        {
+            let mut root = tree.root_mut().unwrap();
+            let child = root.child_entry(0).and_modify(|v| *v += 1).or_insert(0);
+            assert_eq!(child.value(), &3);
+        }
+
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn and_modify_or_insert_inserts_vacant() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        tree.set_root_value(5);
+
+        {
+            let mut root = tree.root_mut().unwrap();
+            let child = root.child_entry(1).and_modify(|v| *v += 1).or_insert(0);
+            assert_eq!(child.value(), &0);
+        }
+
+        assert_eq!(tree.len(), 2);
+    }
+}
@@ -0,0 +1,370 @@
+//! Errors returned by fallible operations on an Eytzinger tree.
+
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// The error returned when a subtree could not be copied.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CopyError {
+    /// There was no node at the source path.
+    VacantSource,
+
+    /// There was no node at the destination parent path.
+    VacantDestinationParent,
+
+    /// There was already a node at the destination.
+    OccupiedDestination,
+
+    /// The destination is within the subtree being copied.
+    DestinationInSource,
+
+    /// A copied node would be placed at an occupied slot while merging.
+    Collision,
+}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            CopyError::VacantSource => "there is no node at the source path",
+            CopyError::VacantDestinationParent => "there is no node at the destination parent path",
+            CopyError::OccupiedDestination => "there is already a node at the destination",
+            CopyError::DestinationInSource => "the destination is within the source subtree",
+            CopyError::Collision => "a copied node collides with an existing node",
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CopyError {}
+
+/// The error returned when a node could not be added because its index can not be addressed or
+/// its storage can not be allocated.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the index of the node exceeds the capacity of the tree")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CapacityError {}
+
+/// The error returned when a child index is not less than the number of children its parent may
+/// have, `max`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ChildIndexError {
+    pub child: usize,
+    pub max: usize,
+}
+
+impl fmt::Display for ChildIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the child index {} should be less than {}",
+            self.child, self.max
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ChildIndexError {}
+
+/// The error returned when a child could not be appended because every child slot of the node is
+/// occupied.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct NodeFull;
+
+impl fmt::Display for NodeFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("every child of the node is occupied")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for NodeFull {}
+
+/// The error returned when a root value could not be set because the tree already has a root.
+/// The rejected value is returned in `value`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RootOccupied<N> {
+    pub value: N,
+}
+
+impl<N> fmt::Display for RootOccupied<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the tree already has a root")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: fmt::Debug> Error for RootOccupied<N> {}
+
+/// The error returned when the values of two nodes could not be swapped.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum SwapError {
+    /// There was no node at `path`.
+    Vacant { path: Vec<usize> },
+}
+
+impl fmt::Display for SwapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwapError::Vacant { path } => write!(f, "there is no node at {:?}", path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SwapError {}
+
+/// The error returned when the maximum number of children per node of a tree could not be
+/// changed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ArityChangeError {
+    /// The tree has nodes other than the root.
+    HasChildren,
+}
+
+impl fmt::Display for ArityChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ArityChangeError::HasChildren => "the tree has nodes other than the root",
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ArityChangeError {}
+
+/// The error returned when a subtree could not be replaced.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ReplaceError {
+    /// There was no node at `path`.
+    Vacant { path: Vec<usize> },
+
+    /// The replacement does not have the same number of children per node as the subtree being
+    /// replaced.
+    ArityMismatch,
+}
+
+impl fmt::Display for ReplaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplaceError::Vacant { path } => write!(f, "there is no node at {:?}", path),
+            ReplaceError::ArityMismatch => {
+                f.write_str("the replacement has a different number of children per node")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ReplaceError {}
+
+/// The error returned when a node could not be spliced out of a tree.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum SpliceError {
+    /// There was no node at `path`.
+    Vacant { path: Vec<usize> },
+
+    /// The node is the root so there is no parent for its children.
+    IsRoot,
+
+    /// The parent does not have enough vacant child slots for the children of the node.
+    InsufficientSlots,
+
+    /// The children of the node can not be moved up a level as the levels have different arities,
+    /// this only happens for trees created with `mixed_arity`.
+    ArityMismatch,
+}
+
+impl fmt::Display for SpliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpliceError::Vacant { path } => write!(f, "there is no node at {:?}", path),
+            SpliceError::IsRoot => f.write_str("the root can not be spliced out"),
+            SpliceError::InsufficientSlots => {
+                f.write_str("the parent does not have enough vacant child slots")
+            }
+            SpliceError::ArityMismatch => {
+                f.write_str("the children can not be moved to a level with a different arity")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SpliceError {}
+
+/// The error returned when the layout of a tree is found to be invalid.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum CorruptionError {
+    /// There is a node at `path` but no node for its parent.
+    OrphanedNode { path: Vec<usize> },
+}
+
+impl fmt::Display for CorruptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorruptionError::OrphanedNode { path } => {
+                write!(f, "there is no parent for the node at {:?}", path)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CorruptionError {}
+
+/// The error returned when a tree could not be built from paths.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum PathError {
+    /// No value was supplied for the parent of the node at `path`.
+    MissingParent { path: Vec<usize> },
+
+    /// A child offset of `path` was not less than the maximum number of children per node.
+    InvalidPath { path: Vec<usize> },
+
+    /// The maximum number of children per node was 0.
+    InvalidArity,
+
+    /// The storage for the node at `path` could not be allocated.
+    TooLarge { path: Vec<usize> },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::MissingParent { path } => {
+                write!(f, "there is no parent for the node at {:?}", path)
+            }
+            PathError::InvalidPath { path } => write!(f, "the path {:?} is invalid", path),
+            PathError::InvalidArity => f.write_str("the arity is 0"),
+            PathError::TooLarge { path } => {
+                write!(f, "the node at {:?} is too large to store", path)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PathError {}
+
+/// The error returned when a tree could not be parsed from the Newick format.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum NewickError {
+    /// An unexpected token, or the end of the input, was found at the byte position `pos`.
+    UnexpectedToken { pos: usize },
+
+    /// The value ending at the byte position `pos` could not be parsed.
+    InvalidValue { pos: usize },
+
+    /// The node whose children include the byte position `pos` has more children than the
+    /// maximum number of children per node.
+    TooManyChildren { pos: usize },
+
+    /// The node starting at the byte position `pos` is nested too deeply to be stored.
+    TooDeep { pos: usize },
+
+    /// The maximum number of children per node was 0.
+    InvalidArity,
+}
+
+impl fmt::Display for NewickError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewickError::UnexpectedToken { pos } => write!(f, "unexpected token at {}", pos),
+            NewickError::InvalidValue { pos } => write!(f, "invalid value at {}", pos),
+            NewickError::TooManyChildren { pos } => write!(f, "too many children at {}", pos),
+            NewickError::TooDeep { pos } => write!(f, "nested too deeply at {}", pos),
+            NewickError::InvalidArity => f.write_str("the arity is 0"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for NewickError {}
+
+/// The error returned when a tree could not be built from nested JSON.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum JsonTreeError {
+    /// The node at `path` was not an object with a `value` and a `children` array.
+    InvalidNode { path: Vec<usize> },
+
+    /// The value of the node at `path` could not be deserialized.
+    InvalidValue { path: Vec<usize> },
+
+    /// The node at `path` has more children than the maximum number of children per node.
+    TooManyChildren { path: Vec<usize> },
+
+    /// The maximum number of children per node was 0.
+    InvalidArity,
+
+    /// The node at `path` could not be stored, either its index can not be represented by a
+    /// `usize` or the storage for it could not be allocated.
+    TooLarge { path: Vec<usize> },
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for JsonTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonTreeError::InvalidNode { path } => write!(f, "invalid node at {:?}", path),
+            JsonTreeError::InvalidValue { path } => write!(f, "invalid value at {:?}", path),
+            JsonTreeError::TooManyChildren { path } => {
+                write!(f, "too many children at {:?}", path)
+            }
+            JsonTreeError::InvalidArity => f.write_str("the arity is 0"),
+            JsonTreeError::TooLarge { path } => {
+                write!(f, "the node at {:?} is too large to store", path)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(feature = "std")]
+impl Error for JsonTreeError {}
+
+/// The error returned when a tree could not be built from a `CompactTree`.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum CompactTreeError {
+    /// The arity was 0.
+    InvalidArity,
+
+    /// An index could not be represented by a `usize` or was not after the previous index.
+    InvalidIndex,
+
+    /// There was no node for the parent of the node at `path`.
+    MissingParent { path: Vec<usize> },
+
+    /// The storage for an index could not be allocated.
+    TooLarge,
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for CompactTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactTreeError::InvalidArity => f.write_str("the arity is 0"),
+            CompactTreeError::InvalidIndex => f.write_str("an index is invalid"),
+            CompactTreeError::MissingParent { path } => {
+                write!(f, "there is no parent for the node at {:?}", path)
+            }
+            CompactTreeError::TooLarge => f.write_str("the tree is too large to allocate"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg(feature = "std")]
+impl Error for CompactTreeError {}
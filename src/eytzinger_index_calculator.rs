@@ -1,43 +1,321 @@
-use std::ops::Range;
+use crate::error::CapacityError;
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct EytzingerIndexCalculator {
     max_children_per_node: usize,
+    /// The levels at the top of the tree in depth order, the arity of the last level also applies
+    /// to every deeper level. This is empty when every level has `max_children_per_node` children
+    /// per node.
+    levels: Vec<Level>,
+}
+
+/// A level of a tree with mixed arities, nodes at this level have `arity` children each.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Level {
+    arity: usize,
+    first_index: usize,
+    width: usize,
 }
 
 impl EytzingerIndexCalculator {
     pub fn new(max_children_per_node: usize) -> Self {
-        assert!(max_children_per_node > 0);
+        assert!(
+            max_children_per_node > 0,
+            "max_children_per_node should be greater than 0"
+        );
 
         Self {
             max_children_per_node,
+            levels: vec![],
+        }
+    }
+
+    /// Creates a calculator where nodes at depth `d` have `level_arities[d]` children, the last
+    /// arity applies to every deeper level.
+    pub fn with_level_arities(mut level_arities: Vec<usize>) -> Self {
+        assert!(
+            level_arities.iter().all(|&arity| arity > 0),
+            "max_children_per_node should be greater than 0"
+        );
+
+        // trailing levels with the same arity as their predecessor are implied
+        while level_arities.len() > 1
+            && level_arities[level_arities.len() - 1] == level_arities[level_arities.len() - 2]
+        {
+            level_arities.pop();
+        }
+
+        let max_children_per_node = *level_arities
+            .iter()
+            .max()
+            .expect("at least one level arity should be specified");
+
+        if level_arities.len() == 1 {
+            return Self::new(max_children_per_node);
+        }
+
+        let mut first_index: usize = 0;
+        let mut width: usize = 1;
+        let levels = level_arities
+            .into_iter()
+            .map(|arity| {
+                let level = Level {
+                    arity,
+                    first_index,
+                    width,
+                };
+                first_index = first_index.saturating_add(width);
+                width = width.saturating_mul(arity);
+                level
+            })
+            .collect();
+
+        Self {
+            max_children_per_node,
+            levels,
+        }
+    }
+
+    /// Gets a calculator for the subtree rooted at the specified index.
+    pub fn subtree(&self, root_index: usize) -> Self {
+        if self.levels.is_empty() {
+            return self.clone();
         }
+
+        let depth = self.depth(root_index).min(self.levels.len() - 1);
+        Self::with_level_arities(self.levels[depth..].iter().map(|l| l.arity).collect())
     }
 
     pub fn max_children_per_node(&self) -> usize {
         self.max_children_per_node
     }
 
+    /// Gets whether every node may have `max_children_per_node` children.
+    pub fn is_uniform(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Gets the number of children the node at the specified index may have.
+    pub fn child_count(&self, parent_index: usize) -> usize {
+        self.level(parent_index).arity
+    }
+
     pub fn child_index(&self, parent_index: usize, child_offset: usize) -> usize {
+        self.try_child_index(parent_index, child_offset)
+            .expect("the child index exceeds the capacity of the tree")
+    }
+
+    /// Gets the index of the child at the specified offset, `Err(CapacityError)` if the index can
+    /// not be represented by a `usize`.
+    pub fn try_child_index(
+        &self,
+        parent_index: usize,
+        child_offset: usize,
+    ) -> Result<usize, CapacityError> {
         assert!(
             child_offset < self.max_children_per_node,
-            "the child index should be less than max_children_per_node"
+            "the child index {} should be less than max_children_per_node ({})",
+            child_offset,
+            self.max_children_per_node
         );
 
-        (parent_index * self.max_children_per_node) + child_offset + 1
+        let level = self.level(parent_index);
+        assert!(
+            child_offset < level.arity,
+            "the child index {} should be less than the arity of its parent's level ({})",
+            child_offset,
+            level.arity
+        );
+
+        // nodes at and below `level` form a forest with `level.width` roots so the children of
+        // a node follow the roots in the same way as for a single root
+        (parent_index - level.first_index)
+            .checked_mul(level.arity)
+            .and_then(|index| index.checked_add(level.first_index))
+            .and_then(|index| index.checked_add(level.width))
+            .and_then(|index| index.checked_add(child_offset))
+            .ok_or(CapacityError)
     }
 
     pub fn parent_index(&self, child_index: usize) -> Option<usize> {
         if child_index == 0 {
-            None
-        } else {
-            Some((child_index - 1) / self.max_children_per_node)
+            return None;
         }
+
+        let position = self.level_position(child_index);
+        let level = self.level_at(position);
+        let parent_index = if position > 0 && child_index - level.first_index < level.width {
+            let parent_level = self.levels[position - 1];
+            parent_level.first_index + (child_index - level.first_index) / parent_level.arity
+        } else {
+            level.first_index + (child_index - level.first_index - level.width) / level.arity
+        };
+
+        Some(parent_index)
     }
 
     pub fn child_indexes(&self, parent_index: usize) -> Range<usize> {
         let first_child_index = self.child_index(parent_index, 0);
 
-        first_child_index..(first_child_index + self.max_children_per_node)
+        first_child_index..(first_child_index + self.child_count(parent_index))
+    }
+
+    /// Gets the path of child offsets from the root to the node at the specified index.
+    pub fn path(&self, index: usize) -> Vec<usize> {
+        let mut path = vec![];
+        let mut current = index;
+
+        while let Some(parent_index) = self.parent_index(current) {
+            path.push(current - self.child_index(parent_index, 0));
+            current = parent_index;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Gets the index for the specified path of child offsets from the root, `None` if any child
+    /// offset is out of range or the index cannot be addressed.
+    pub fn path_index(&self, path: &[usize]) -> Option<usize> {
+        path.iter().try_fold(0usize, |index, &child| {
+            if child < self.child_count(index) {
+                self.try_child_index(index, child).ok()
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Gets the range of indexes which nodes at the specified depth occupy. The range saturates
+    /// at `usize::MAX` for depths which cannot be addressed.
+    pub fn depth_indexes(&self, depth: usize) -> Range<usize> {
+        let position = depth.min(self.levels.len().saturating_sub(1));
+        let level = self.level_at(position);
+        let mut first_index = level.first_index;
+        let mut width = level.width;
+
+        for _ in position..depth {
+            first_index = first_index.saturating_add(width);
+            width = width.saturating_mul(level.arity);
+        }
+
+        first_index..first_index.saturating_add(width)
+    }
+
+    /// Gets the depth of the node at the specified index.
+    pub fn depth(&self, index: usize) -> usize {
+        let mut depth = 0;
+        let mut current = index;
+
+        while let Some(parent_index) = self.parent_index(current) {
+            depth += 1;
+            current = parent_index;
+        }
+
+        depth
+    }
+
+    /// Gets the deepest level which starts at or before the specified index.
+    fn level(&self, index: usize) -> Level {
+        self.level_at(self.level_position(index))
+    }
+
+    fn level_position(&self, index: usize) -> usize {
+        self.levels
+            .partition_point(|level| level.first_index <= index)
+            .saturating_sub(1)
+    }
+
+    fn level_at(&self, position: usize) -> Level {
+        self.levels.get(position).copied().unwrap_or(Level {
+            arity: self.max_children_per_node,
+            first_index: 0,
+            width: 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EytzingerIndexCalculator;
+    use crate::error::CapacityError;
+
+    #[test]
+    fn path() {
+        let calculator = EytzingerIndexCalculator::new(2);
+
+        assert_eq!(calculator.path(0), Vec::<usize>::new());
+        assert_eq!(calculator.path(2), vec![1]);
+        assert_eq!(calculator.path(9), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn try_child_index_overflow() {
+        let calculator = EytzingerIndexCalculator::new(2);
+
+        assert_eq!(calculator.try_child_index(3, 1), Ok(8));
+        assert_eq!(
+            calculator.try_child_index(usize::MAX / 2, 1),
+            Err(CapacityError)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the child index exceeds the capacity of the tree")]
+    fn child_index_overflow_panics() {
+        EytzingerIndexCalculator::new(2).child_index(usize::MAX / 2, 1);
+    }
+
+    #[test]
+    fn path_index() {
+        let calculator = EytzingerIndexCalculator::new(2);
+
+        assert_eq!(calculator.path_index(&[]), Some(0));
+        assert_eq!(calculator.path_index(&[0, 1, 0]), Some(9));
+        assert_eq!(calculator.path_index(&[2]), None);
+        assert_eq!(calculator.path_index(&[1; 64]), None);
+    }
+
+    #[test]
+    fn depth_indexes() {
+        let calculator = EytzingerIndexCalculator::new(2);
+
+        assert_eq!(calculator.depth_indexes(0), 0..1);
+        assert_eq!(calculator.depth_indexes(1), 1..3);
+        assert_eq!(calculator.depth_indexes(3), 7..15);
+    }
+
+    #[test]
+    fn level_arities() {
+        let calculator = EytzingerIndexCalculator::with_level_arities(vec![3, 2]);
+
+        assert_eq!(calculator.max_children_per_node(), 3);
+        assert_eq!(calculator.child_indexes(0), 1..4);
+        assert_eq!(calculator.child_indexes(2), 6..8);
+        assert_eq!(calculator.child_indexes(5), 12..14);
+        assert_eq!(calculator.parent_index(7), Some(2));
+        assert_eq!(calculator.parent_index(15), Some(6));
+        assert_eq!(calculator.path(15), vec![1, 0, 1]);
+        assert_eq!(calculator.path_index(&[1, 0, 1]), Some(15));
+        assert_eq!(calculator.path_index(&[0, 2]), None);
+        assert_eq!(calculator.depth_indexes(2), 4..10);
+        assert_eq!(calculator.depth_indexes(3), 10..22);
+    }
+
+    #[test]
+    fn level_arities_collapse_to_uniform() {
+        assert_eq!(
+            EytzingerIndexCalculator::with_level_arities(vec![2, 2]),
+            EytzingerIndexCalculator::new(2)
+        );
+    }
+
+    #[test]
+    fn depth_indexes_arity_one() {
+        let calculator = EytzingerIndexCalculator::new(1);
+
+        assert_eq!(calculator.depth_indexes(4), 4..5);
     }
 }
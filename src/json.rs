@@ -0,0 +1,243 @@
+use crate::{error::JsonTreeError, DepthFirstOrder, EytzingerTree};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+impl<N> EytzingerTree<N> {
+    /// Builds a tree from the nested JSON produced by `to_json_tree`.
+    ///
+    /// # Errors
+    ///
+    /// * `JsonTreeError::InvalidNode` if a node is not an object with a `value` and a `children`
+    ///   array.
+    /// * `JsonTreeError::InvalidValue` if a value could not be deserialized.
+    /// * `JsonTreeError::TooManyChildren` if a node has more than `max_children_per_node`
+    ///   children.
+    /// * `JsonTreeError::InvalidArity` if `max_children_per_node` is 0.
+    /// * `JsonTreeError::TooLarge` if a node can not be stored, as the storage is dense this may
+    ///   happen for deeply nested input with few nodes.
+    pub fn from_json_tree(json: &Value, max_children_per_node: usize) -> Result<Self, JsonTreeError>
+    where
+        N: DeserializeOwned,
+    {
+        if max_children_per_node == 0 {
+            return Err(JsonTreeError::InvalidArity);
+        }
+
+        let mut tree = EytzingerTree::new(max_children_per_node);
+
+        // the index of every node is found before any storage is allocated so deeply nested input
+        // fails without first growing the tree
+        let mut json_nodes = vec![];
+        let mut pending = vec![(0, json, vec![])];
+        while let Some((index, json_node, path)) = pending.pop() {
+            if json_node.is_null() {
+                continue;
+            }
+
+            let (value, children) = match (json_node.get("value"), json_node.get("children")) {
+                (Some(value), Some(Value::Array(children))) => (value, children),
+                _ => return Err(JsonTreeError::InvalidNode { path }),
+            };
+
+            if children.len() > max_children_per_node {
+                return Err(JsonTreeError::TooManyChildren { path });
+            }
+
+            for (child_offset, child) in children.iter().enumerate() {
+                if child.is_null() {
+                    continue;
+                }
+
+                let mut child_path = path.clone();
+                child_path.push(child_offset);
+
+                match tree.index_calculator.try_child_index(index, child_offset) {
+                    Ok(child_index) => pending.push((child_index, child, child_path)),
+                    Err(_) => return Err(JsonTreeError::TooLarge { path: child_path }),
+                }
+            }
+
+            json_nodes.push((index, value, path));
+        }
+
+        if let Some((index, _, path)) = json_nodes.iter().max_by_key(|(index, ..)| *index) {
+            tree.try_reserve_index(*index)
+                .map_err(|_| JsonTreeError::TooLarge { path: path.clone() })?;
+        }
+
+        for (index, value, path) in json_nodes {
+            let value = N::deserialize(value).map_err(|_| JsonTreeError::InvalidValue { path })?;
+            tree.set_value(index, value);
+        }
+
+        Ok(tree)
+    }
+
+    /// Converts the tree into nested JSON which is independent of the underlying storage.
+    ///
+    /// Each node is an object with a `value` and a `children` array. The `children` array
+    /// contains each child slot up to and including the last occupied one, with `null` for any
+    /// vacant slots before it. A leaf has an empty `children` array and an empty tree is `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value could not be serialized.
+    pub fn to_json_tree(&self) -> serde_json::Result<Value>
+    where
+        N: Serialize,
+    {
+        let root = match self.root() {
+            Some(root) => root,
+            None => return Ok(Value::Null),
+        };
+
+        let mut json_nodes: Vec<Option<Value>> = vec![None; self.nodes.len()];
+
+        for node in root.depth_first_iter(DepthFirstOrder::PostOrder) {
+            let children = match self.last_child_offset(node.index()) {
+                Some(last_child_offset) => self
+                    .child_indexes(node.index())
+                    .take(last_child_offset + 1)
+                    .map(|child_index| json_nodes[child_index].take().unwrap_or(Value::Null))
+                    .collect(),
+                None => vec![],
+            };
+
+            let mut json_node = Map::new();
+            json_node.insert("value".to_owned(), serde_json::to_value(node.value())?);
+            json_node.insert("children".to_owned(), Value::Array(children));
+
+            json_nodes[node.index()] = Some(Value::Object(json_node));
+        }
+
+        Ok(json_nodes[0]
+            .take()
+            .expect("the root should have been converted last"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::JsonTreeError, test_util::sample_tree, EytzingerTree};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn to_json_tree() {
+        let json = sample_tree().to_json_tree().unwrap();
+
+        assert_eq!(json["children"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            json,
+            json!({
+                "value": 5,
+                "children": [
+                    {
+                        "value": 2,
+                        "children": [
+                            { "value": 1, "children": [] },
+                            {
+                                "value": 4,
+                                "children": [{ "value": 3, "children": [] }]
+                            }
+                        ]
+                    },
+                    {
+                        "value": 7,
+                        "children": [null, { "value": 8, "children": [] }]
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn to_json_tree_empty() {
+        let tree = EytzingerTree::<u32>::new(2);
+
+        assert_eq!(tree.to_json_tree().unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn from_json_tree_round_trips() {
+        let tree = sample_tree();
+
+        let json = tree.to_json_tree().unwrap();
+        let parsed = EytzingerTree::<u32>::from_json_tree(&json, 2).unwrap();
+
+        assert_eq!(parsed, tree);
+    }
+
+    #[test]
+    fn from_json_tree_empty() {
+        let parsed = EytzingerTree::<u32>::from_json_tree(&Value::Null, 2).unwrap();
+
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn from_json_tree_too_many_children() {
+        let json = json!({
+            "value": 5,
+            "children": [
+                {
+                    "value": 2,
+                    "children": [null, null, { "value": 1, "children": [] }]
+                }
+            ]
+        });
+
+        assert_eq!(
+            EytzingerTree::<u32>::from_json_tree(&json, 2),
+            Err(JsonTreeError::TooManyChildren { path: vec![0] })
+        );
+    }
+
+    #[test]
+    fn from_json_tree_too_large() {
+        let chain = |depth: usize, child_offset: usize| {
+            (0..depth).fold(json!({ "value": 0, "children": [] }), |child, value| {
+                let mut children = vec![Value::Null; child_offset];
+                children.push(child);
+                json!({ "value": value, "children": children })
+            })
+        };
+
+        // the index of a node at depth 65 of a binary tree can not be represented by a usize
+        assert_eq!(
+            EytzingerTree::<u32>::from_json_tree(&chain(70, 0), 2),
+            Err(JsonTreeError::TooLarge { path: vec![0; 65] })
+        );
+
+        // the index of the node at depth 62 can be represented but not allocated
+        assert_eq!(
+            EytzingerTree::<u32>::from_json_tree(&chain(62, 1), 2),
+            Err(JsonTreeError::TooLarge { path: vec![1; 62] })
+        );
+
+        let tree = EytzingerTree::<u32>::from_json_tree(&chain(16, 1), 2).unwrap();
+        assert_eq!(tree.len(), 17);
+    }
+
+    #[test]
+    fn from_json_tree_invalid_arity() {
+        assert_eq!(
+            EytzingerTree::<u32>::from_json_tree(&Value::Null, 0),
+            Err(JsonTreeError::InvalidArity)
+        );
+    }
+
+    #[test]
+    fn from_json_tree_invalid() {
+        let json = json!({ "value": 5, "children": [{ "value": 2 }] });
+        assert_eq!(
+            EytzingerTree::<u32>::from_json_tree(&json, 2),
+            Err(JsonTreeError::InvalidNode { path: vec![0] })
+        );
+
+        let json = json!({ "value": "5", "children": [] });
+        assert_eq!(
+            EytzingerTree::<u32>::from_json_tree(&json, 2),
+            Err(JsonTreeError::InvalidValue { path: vec![] })
+        );
+    }
+}
@@ -1,33 +1,126 @@
+//! An Eytzinger tree implementation, this is an N-tree implemented using array storage.
+//!
+//! # Features
+//!
+//! The `std` feature is enabled by default, without it the crate is `no_std` and only requires
+//! `alloc`. The following require the `std` feature:
+//!
+//! * `SparseEytzingerTree`, which is backed by a `HashMap`.
+//! * The `std::error::Error` implementations of the types in the `error` module.
+//! * The `serde` and `rayon` features.
+//!
+//! Internal diagnostic messages are emitted through `log` by default, enabling the `tracing`
+//! feature emits them through `tracing` instead.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod diagnostics;
+
 mod eytzinger_index_calculator;
 pub(crate) use self::eytzinger_index_calculator::EytzingerIndexCalculator;
 
+mod occupancy;
+pub(crate) use self::occupancy::Occupancy;
+
 mod node_mut;
 pub use self::node_mut::NodeMut;
 
 mod node;
 pub use self::node::Node;
 
+mod cursor;
+pub use self::cursor::Cursor;
+
+mod neighbors;
+pub use self::neighbors::Neighbors;
+
+mod builder;
+pub use self::builder::TreeBuilder;
+
+mod overwrite_policy;
+pub use self::overwrite_policy::OverwritePolicy;
+
+mod metadata;
+pub use self::metadata::MetadataTree;
+
+#[cfg(feature = "std")]
+mod sparse;
+#[cfg(feature = "std")]
+pub use self::sparse::SparseEytzingerTree;
+
+mod tree_storage;
+pub use self::tree_storage::TreeStorage;
+
+mod storage_node;
+pub use self::storage_node::StorageNode;
+
+#[cfg(feature = "std")]
+mod binary;
+#[cfg(feature = "std")]
+pub use self::binary::BinaryValue;
+
 pub mod entry;
+pub mod error;
 pub mod traversal;
 
+mod newick;
+
+mod pretty;
+
+#[cfg(feature = "serde")]
+mod json;
+
+#[cfg(feature = "serde")]
+mod compact;
+#[cfg(feature = "serde")]
+pub use self::compact::CompactTree;
+
+#[cfg(feature = "rayon")]
+mod par_iter;
+
+#[cfg(test)]
+mod test_util;
+
 use crate::{
-    entry::{Entry, VacantEntry},
+    entry::{Entry, EntryRef, VacantEntry, VacantEntryRef},
+    error::{
+        ArityChangeError, CapacityError, CopyError, CorruptionError, PathError, ReplaceError,
+        RootOccupied, SpliceError, SwapError,
+    },
     traversal::{
         BreadthFirstIter, BreadthFirstIterator, DepthFirstIter, DepthFirstIterator,
-        DepthFirstOrder, NodeChildIter,
+        DepthFirstOrder, NodeChildIter, TreeEvent, TreeVisitor,
     },
 };
-use std::{
-    cmp::PartialEq,
+use alloc::{collections::VecDeque, format, string::String, vec, vec::Vec};
+use core::{
+    cmp::{Ordering, PartialEq},
+    fmt::Debug,
     hash::{Hash, Hasher},
-    mem,
+    iter, mem,
     ops::Range,
+    ptr,
 };
+#[cfg(feature = "std")]
+use std::collections::{hash_map::DefaultHasher, HashMap};
 
 /// An Eytzinger tree is an N-tree stored in an array structure.
+///
+/// # Memory
+///
+/// Each node occupies a single `Option<N>` slot of the underlying storage, the position of a node
+/// is implied by its slot so no parent, child or index values are stored per node. The storage
+/// must however contain a slot for every position up to the deepest, right-most node, so a node
+/// at depth `d` requires roughly `max_children_per_node^d` slots. The number of addressable slots
+/// is limited by `usize` and by the maximum size of a `Vec`. A `SparseEytzingerTree` may be used
+/// for trees with a low `density`.
 #[derive(Debug, Clone, Eq)]
 pub struct EytzingerTree<N> {
     nodes: Vec<Option<N>>,
+    occupancy: Occupancy,
     index_calculator: EytzingerIndexCalculator,
     len: usize,
 }
@@ -40,537 +133,4429 @@ impl<N: PartialEq> PartialEq for EytzingerTree<N> {
     }
 }
 
+impl<N: PartialOrd> PartialOrd for EytzingerTree<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.index_calculator.cmp(&other.index_calculator) {
+            Ordering::Equal => self.iter_paths().partial_cmp(other.iter_paths()),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+/// Trees are ordered first by `max_children_per_node` and the arity of each level, then
+/// lexicographically by their breadth-first sequences of `(path, value)` pairs, as returned by
+/// `iter_paths`. Each path is the sequence of child offsets from the root so the ordering depends
+/// on the structure and values of the tree but not on the capacity of its storage.
+impl<N: Ord> Ord for EytzingerTree<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index_calculator
+            .cmp(&other.index_calculator)
+            .then_with(|| self.iter_paths().cmp(other.iter_paths()))
+    }
+}
+
+/// Trees are hashed by `max_children_per_node`, `len` and the sequence of occupied nodes. Each
+/// node is hashed by its index, which identifies its path for a given `max_children_per_node`,
+/// and its value, so vacant slots in the storage do not affect the hash.
 impl<N: Hash> Hash for EytzingerTree<N> {
     fn hash<H>(&self, state: &mut H)
     where
         H: Hasher,
     {
+        self.index_calculator.hash(state);
+        self.len.hash(state);
         for indexed_value in self.enumerate_values() {
             indexed_value.hash(state);
         }
-        self.index_calculator.hash(state);
-        self.len.hash(state);
+    }
+}
+
+/// Iterates over the nodes of the tree in breadth-first order, the same as `breadth_first_iter`.
+impl<'a, N> IntoIterator for &'a EytzingerTree<N> {
+    type Item = Node<'a, N>;
+    type IntoIter = BreadthFirstIter<'a, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.breadth_first_iter()
     }
 }
 
 impl<N> EytzingerTree<N> {
-    /// Creates a new Eytzinger tree with the specified maximum number of child nodes per parent.
+    /// Creates a new Eytzinger tree with the specified maximum number of child nodes per parent. A
+    /// maximum of 1 creates a tree where each node has at most one child, a linked list.
+    ///
+    /// # Panics
+    ///
+    /// Panics with "max_children_per_node should be greater than 0" if `max_children_per_node`
+    /// is 0.
     ///
     /// # Returns
     ///
     /// The new Eytzinger tree.
     pub fn new(max_children_per_node: usize) -> Self {
-        Self {
-            nodes: vec![],
-            index_calculator: EytzingerIndexCalculator::new(max_children_per_node),
-            len: 0,
-        }
+        Self::with_index_calculator(EytzingerIndexCalculator::new(max_children_per_node))
     }
 
-    /// Gets a depth-first iterator over all nodes.
-    pub fn depth_first_iter(&self, order: DepthFirstOrder) -> DepthFirstIter<N> {
-        DepthFirstIter::new(self, self.root(), order)
+    /// Creates a new Eytzinger tree where the maximum number of child nodes per parent depends on
+    /// the depth of the parent. Nodes at depth `d` may have `level_arities[d]` children, the last
+    /// arity applies to every deeper level. `max_children_per_node` is the largest of the arities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level_arities` is empty or contains a 0.
+    ///
+    /// # Returns
+    ///
+    /// The new Eytzinger tree.
+    pub fn mixed_arity(level_arities: Vec<usize>) -> Self {
+        Self::with_index_calculator(EytzingerIndexCalculator::with_level_arities(level_arities))
     }
 
-    /// Gets a breadth-first iterator over all nodes.
-    pub fn breadth_first_iter(&self) -> BreadthFirstIter<N> {
-        BreadthFirstIter::new(self, self.root())
-    }
+    /// Creates a new perfect Eytzinger tree with the specified maximum number of child nodes per
+    /// parent, every level down to and including `depth` will be full.
+    ///
+    /// The value of each node is created by calling `f` with the path of child offsets from the
+    /// root to that node.
+    ///
+    /// # Returns
+    ///
+    /// The new Eytzinger tree.
+    pub fn perfect_from_fn<F>(max_children_per_node: usize, depth: usize, mut f: F) -> Self
+    where
+        F: FnMut(&[usize]) -> N,
+    {
+        let index_calculator = EytzingerIndexCalculator::new(max_children_per_node);
+        let len = index_calculator.depth_indexes(depth).end;
 
-    pub fn into_depth_first_iterator(self, order: DepthFirstOrder) -> DepthFirstIterator<N> {
-        DepthFirstIterator::new(self, order)
-    }
+        let nodes = (0..len)
+            .map(|index| Some(f(&index_calculator.path(index))))
+            .collect();
 
-    pub fn into_breadth_first_iterator(self) -> BreadthFirstIterator<N> {
-        BreadthFirstIterator::new(self)
-    }
+        let mut occupancy = Occupancy::new();
+        for index in 0..len {
+            occupancy.insert(index);
+        }
 
-    /// Gets whether the Eytzinger tree is empty.
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        Self {
+            nodes,
+            occupancy,
+            index_calculator,
+            len,
+        }
     }
 
-    /// Gets the number of nodes in the Eytzinger tree.
-    pub fn len(&self) -> usize {
-        self.len
-    }
+    /// Creates a new Eytzinger tree with the specified maximum number of child nodes per parent by
+    /// expanding from `root` in breadth-first order. `f` is called with the value of each node
+    /// above `max_depth` and each child index to create the value of that child, `None` leaves
+    /// the child slot vacant.
+    ///
+    /// # Returns
+    ///
+    /// The new Eytzinger tree.
+    pub fn expand_from<F>(root: N, max_children_per_node: usize, max_depth: usize, mut f: F) -> Self
+    where
+        F: FnMut(&N, usize) -> Option<N>,
+    {
+        let mut tree = EytzingerTree::new(max_children_per_node);
+        tree.set_value(0, root);
 
-    /// Gets the maximum number of children per parent node.
-    pub fn max_children_per_node(&self) -> usize {
-        self.index_calculator.max_children_per_node()
-    }
+        let mut pending = VecDeque::new();
+        pending.push_back((0, 0));
+        while let Some((index, depth)) = pending.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
 
-    /// Clears the Eytzinger tree, removing all nodes.
-    pub fn clear(&mut self) {
-        self.remove_root_value();
-    }
+            for child_offset in 0..tree.child_count(index) {
+                let parent_value = tree.nodes[index]
+                    .as_ref()
+                    .expect("a value should exist at an expanded index");
 
-    /// Gets the root node, `None` if there was no root node.
-    ///
-    /// The root node may be set with `set_root_value`.
-    pub fn root(&self) -> Option<Node<N>> {
-        self.node(0)
-    }
+                if let Some(child_value) = f(parent_value, child_offset) {
+                    let child_index = tree.child_index(index, child_offset);
+                    tree.set_value(child_index, child_value);
+                    pending.push_back((child_index, depth + 1));
+                }
+            }
+        }
 
-    /// Gets the mutable root node, `None` if there was no root node.
-    ///
-    /// The root node may be set with `set_root_value`.
-    pub fn root_mut(&mut self) -> Option<NodeMut<N>> {
-        self.node_mut(0).ok()
+        tree
     }
 
-    /// Sets the value of the root node. All child nodes will remain as they are.
+    /// Creates a new complete Eytzinger tree from values in the same layout as the tree's storage,
+    /// as used by a binary heap. The children of the value at index `i` are at the indexes
+    /// `i * max_children_per_node + 1` onwards, so the levels are filled in order from left to
+    /// right.
     ///
     /// # Returns
     ///
-    /// The new root node.
-    pub fn set_root_value(&mut self, new_value: N) -> NodeMut<N> {
-        self.set_value(0, new_value)
-    }
+    /// The new Eytzinger tree.
+    pub fn from_complete_vec(max_children_per_node: usize, values: Vec<N>) -> Self {
+        let len = values.len();
 
-    /// Removes the root value. This will also remove all children.
-    ///
-    /// # Returns
-    ///
-    /// The old root value if there was one.
-    pub fn remove_root_value(&mut self) -> (Option<N>, VacantEntry<N>) {
-        self.nodes.truncate(1);
-        self.len = 0;
-        let value = self.nodes[0].take();
+        let mut occupancy = Occupancy::new();
+        for index in 0..len {
+            occupancy.insert(index);
+        }
 
-        (
-            value,
-            VacantEntry {
-                tree: self,
-                index: 0,
-            },
-        )
+        Self {
+            nodes: values.into_iter().map(Some).collect(),
+            occupancy,
+            index_calculator: EytzingerIndexCalculator::new(max_children_per_node),
+            len,
+        }
     }
 
-    /// Gets the entry for the root node.
-    ///
-    /// # Examples
-    ///
-    /// ```    
-    /// use lz_eytzinger_tree::{EytzingerTree, entry::Entry};
+    /// Creates a new Eytzinger tree from pairs of paths of child offsets from the root and values.
+    /// The pairs may be in any order, if multiple values are supplied for the same path the last
+    /// one is kept.
     ///
-    /// let tree = {
-    ///     let mut tree = EytzingerTree::<u32>::new(8);
-    ///     tree.root_entry().or_insert(5);
-    ///     tree
-    /// };
+    /// # Errors
     ///
-    /// let root = tree.root().unwrap();
-    /// assert_eq!(root.value(), &5);
-    /// ```
-    pub fn root_entry(&mut self) -> Entry<N> {
-        self.entry(0)
-    }
-
-    /// Builds a new `EytzingerTree<N>` with the values mapped
-    /// using the specified selector.
-    pub fn map<U, F>(self, mut f: F) -> EytzingerTree<U>
+    /// * `PathError::MissingParent` if no value was supplied for the parent of a path.
+    /// * `PathError::InvalidPath` if a child offset is not less than `max_children_per_node`.
+    /// * `PathError::InvalidArity` if `max_children_per_node` is 0.
+    /// * `PathError::TooLarge` if the storage for the deepest path could not be allocated.
+    pub fn from_paths<I>(max_children_per_node: usize, pairs: I) -> Result<Self, PathError>
     where
-        F: FnMut(N) -> U,
+        I: IntoIterator<Item = (Vec<usize>, N)>,
     {
-        let nodes = self.nodes.into_iter().map(|n| n.map(&mut f)).collect();
+        if max_children_per_node == 0 {
+            return Err(PathError::InvalidArity);
+        }
 
-        EytzingerTree {
-            nodes: nodes,
-            index_calculator: self.index_calculator,
-            len: self.len,
+        let mut tree = EytzingerTree::new(max_children_per_node);
+
+        // insert shorter paths first so parents are inserted before their children
+        let mut pairs: Vec<_> = pairs.into_iter().collect();
+        pairs.sort_by_key(|(path, _)| path.len());
+
+        let mut indexed_pairs = Vec::with_capacity(pairs.len());
+        for (path, value) in pairs {
+            match tree.path_index(&path) {
+                Some(index) => indexed_pairs.push((index, path, value)),
+                None => return Err(PathError::InvalidPath { path }),
+            }
         }
-    }
 
-    /// Shrinks the inner storage of the tree to only take up
-    /// as much space as required.
-    pub fn shrink_to_fit(&mut self) {
-        self.nodes.truncate(self.len())
-    }
+        // reserve the storage for every path up front so deep paths fail before the tree grows
+        if let Some((index, path, _)) = indexed_pairs.iter().max_by_key(|(index, ..)| *index) {
+            tree.try_reserve_index(*index)
+                .map_err(|_| PathError::TooLarge { path: path.clone() })?;
+        }
 
-    /// Gets an iterator over each value and its index in the tree.
-    fn enumerate_values(&self) -> impl Iterator<Item = (usize, &N)> {
-        self.nodes
-            .iter()
-            .enumerate()
-            .flat_map(|(i, o)| o.as_ref().map(|v| (i, v)))
+        for (index, path, value) in indexed_pairs {
+            if let Some(parent_index) = tree.parent_index(index) {
+                if tree.node(parent_index).is_none() {
+                    return Err(PathError::MissingParent { path });
+                }
+            }
+
+            tree.set_value(index, value);
+        }
+
+        Ok(tree)
     }
 
-    fn set_child_value(&mut self, parent: usize, child: usize, new_value: N) -> NodeMut<N> {
-        let child_index = self.child_index(parent, child);
-        self.set_value(child_index, new_value)
+    /// Gets a depth-first iterator over all nodes.
+    pub fn depth_first_iter(&self, order: DepthFirstOrder) -> DepthFirstIter<'_, N> {
+        DepthFirstIter::new(self, self.root(), order)
     }
 
-    fn ensure_size(&mut self, index: usize) {
-        let desired_len = index.checked_add(1).expect("index overflow");
+    /// Gets an iterator over the events of a depth-first walk over all nodes. Each node produces
+    /// a `TreeEvent::Enter` before the events of its children and a `TreeEvent::Leave` after
+    /// them, so a leaf produces an `Enter` immediately followed by its `Leave`.
+    pub fn events_iter(&self) -> impl Iterator<Item = TreeEvent<'_, N>> {
+        let mut pending_enter = self.root();
+        let mut nodes: Vec<NodeChildIter<'_, N>> = vec![];
 
-        if let Some(additional) = desired_len.checked_sub(self.nodes.len()) {
-            // TODO LH Use resize_default once stable
-            self.nodes.reserve(additional);
+        iter::from_fn(move || {
+            if let Some(root) = pending_enter.take() {
+                nodes.push(root.child_iter());
+                return Some(TreeEvent::Enter(root));
+            }
 
-            for _ in 0..additional {
-                self.nodes.push(None);
+            let current = nodes.last_mut()?;
+            match current.next() {
+                Some(child) => {
+                    nodes.push(child.child_iter());
+                    Some(TreeEvent::Enter(child))
+                }
+                None => {
+                    let node = current.node();
+                    nodes.pop();
+                    Some(TreeEvent::Leave(node))
+                }
             }
-        }
+        })
     }
 
-    fn remove(&mut self, index: usize) -> Option<N> {
-        if index >= self.nodes.len() {
-            return None;
-        }
-
-        let indices_to_remove: Vec<_> = self
-            .node(index)?
-            .depth_first_iter(DepthFirstOrder::PostOrder)
-            .skip(1)
-            .map(|n| n.index())
-            .collect();
-
-        for index_to_remove in indices_to_remove {
-            let removed_child_value = self.nodes[index_to_remove].take();
-            if removed_child_value.is_some() {
-                self.len -= 1
+    /// Walks all nodes depth-first calling `TreeVisitor::enter` for each node before its children
+    /// are visited and `TreeVisitor::leave` after them, this is the same order as `events_iter`.
+    pub fn visit<V>(&self, visitor: &mut V)
+    where
+        V: TreeVisitor<N>,
+    {
+        for event in self.events_iter() {
+            match event {
+                TreeEvent::Enter(node) => visitor.enter(&node),
+                TreeEvent::Leave(node) => visitor.leave(&node),
             }
         }
+    }
 
-        let old_value = self.nodes[index].take();
+    /// Gets a breadth-first iterator over all nodes.
+    pub fn breadth_first_iter(&self) -> BreadthFirstIter<'_, N> {
+        BreadthFirstIter::new(self, self.root())
+    }
 
-        if old_value.is_some() {
-            self.len -= 1;
-        }
+    /// Gets an iterator over all nodes by walking the underlying storage in order, skipping any
+    /// vacant slots.
+    ///
+    /// Each level of the tree is stored contiguously from left to right so this returns nodes in
+    /// the same order as `breadth_first_iter` for any tree, including those with vacant slots. It
+    /// does not need to track pending nodes so is cheaper than `breadth_first_iter`.
+    pub fn breadth_first_iter_dense(&self) -> impl Iterator<Item = Node<'_, N>> {
+        self.occupancy
+            .iter()
+            .map(move |index| Node { tree: self, index })
+    }
 
-        old_value
+    /// Gets an iterator over all nodes in breadth-first order which checks the layout of the tree
+    /// as it goes. Unlike the other iterators this does not skip a node whose parent is vacant,
+    /// which should never happen but would indicate a corrupted layout, instead an error is returned
+    /// in its place.
+    pub fn checked_breadth_first_iter(
+        &self,
+    ) -> impl Iterator<Item = Result<Node<'_, N>, CorruptionError>> {
+        self.occupancy.iter().map(move |index| {
+            let node = Node { tree: self, index };
+            match self.parent_index(index) {
+                Some(parent_index) if self.node(parent_index).is_none() => {
+                    Err(CorruptionError::OrphanedNode {
+                        path: self.index_calculator.path(index),
+                    })
+                }
+                _ => Ok(node),
+            }
+        })
     }
 
-    fn split_off(&mut self, index: usize) -> EytzingerTree<N> {
-        let mut new_tree = EytzingerTree::new(self.max_children_per_node());
+    /// Gets an iterator over all nodes in reverse breadth-first order. The deepest level is
+    /// returned first and each level is returned from right to left.
+    pub fn breadth_first_iter_rev(&self) -> impl Iterator<Item = Node<'_, N>> {
+        // the flat layout is already in breadth-first order so it can be walked backwards
+        (0..self.nodes.len())
+            .rev()
+            .filter_map(move |index| self.node(index))
+    }
 
-        // get all of the indexes which should be moved out of the source tree
-        let indexes_to_move = self.node(index).map(|n| {
-            n.depth_first_iter(DepthFirstOrder::PreOrder)
-                .map(|n| n.index())
-                .collect::<Vec<_>>()
-        });
+    /// Gets an iterator over all nodes in breadth-first order along with their parent, the parent is
+    /// `None` for the root.
+    pub fn iter_with_parent(&self) -> impl Iterator<Item = (Option<Node<'_, N>>, Node<'_, N>)> {
+        self.breadth_first_iter_dense()
+            .map(|node| (node.parent(), node))
+    }
 
-        if let Some(indexes_to_move) = indexes_to_move {
-            let mut indexes_to_move_iter = indexes_to_move.into_iter();
+    /// Gets an iterator over the paths from the root to each leaf, the leaves are in pre-order.
+    /// Each path starts with the root and ends with the leaf.
+    pub fn root_to_leaf_paths(&self) -> impl Iterator<Item = Vec<Node<'_, N>>> {
+        self.depth_first_iter(DepthFirstOrder::PreOrder)
+            .filter(|node| node.child_iter().next().is_none())
+            .map(|leaf| {
+                let mut path: Vec<_> = iter::successors(Some(leaf), Node::parent).collect();
+                path.reverse();
+                path
+            })
+    }
 
-            if let Some(index_to_move) = indexes_to_move_iter.next() {
-                let new_root_value = self.nodes[index_to_move]
-                    .take()
-                    .expect("there should be a value at the index returned by the iterator");
+    /// Calls `f` with the values along each path from the root to a leaf, as returned by
+    /// `root_to_leaf_paths`, and collects the results.
+    pub fn fold_paths<B, F>(&self, mut f: F) -> Vec<B>
+    where
+        F: FnMut(&[&N]) -> B,
+    {
+        let mut values = Vec::new();
+        self.root_to_leaf_paths()
+            .map(|path| {
+                values.clear();
+                values.extend(path.iter().map(|node| node.value()));
+                f(&values)
+            })
+            .collect()
+    }
 
-                self.len -= 1;
+    /// Gets an iterator over the interior nodes in breadth-first order, these are the nodes with at
+    /// least one child.
+    pub fn interior_iter(&self) -> impl Iterator<Item = Node<'_, N>> {
+        self.breadth_first_iter_dense()
+            .filter(|node| node.child_iter().next().is_some())
+    }
 
-                let mut new_node = new_tree.set_root_value(new_root_value);
+    /// Gets the nodes with at least one vacant child slot in breadth-first order, these are the
+    /// nodes which may still be expanded. This includes every leaf along with any interior node
+    /// which does not have as many children as it may have.
+    pub fn frontier(&self) -> Vec<Node<'_, N>> {
+        self.breadth_first_iter_dense()
+            .filter(|node| node.has_vacant_child().is_some())
+            .collect()
+    }
 
-                // this is used to determine if we need to move up a level
-                let mut previous_parent = self.parent_index(index_to_move);
+    /// Gets a breadth-first iterator over the nodes at a depth of at most `max_depth`, the root
+    /// being at depth 0. Children of nodes at `max_depth` are never visited.
+    pub fn breadth_first_iter_to_depth(
+        &self,
+        max_depth: usize,
+    ) -> impl Iterator<Item = Node<'_, N>> {
+        let mut pending: VecDeque<_> = self
+            .root()
+            .map(|root| (root.index, 0))
+            .into_iter()
+            .collect();
 
-                for index_to_move in indexes_to_move_iter {
-                    let value_to_move = self.nodes[index_to_move]
-                        .take()
-                        .expect("there should be a value at the index returned by the iterator");
+        iter::from_fn(move || {
+            let (index, depth) = pending.pop_front()?;
 
-                    self.len -= 1;
+            if depth < max_depth {
+                pending.extend(
+                    self.child_indexes(index)
+                        .filter(|&child_index| self.node(child_index).is_some())
+                        .map(|child_index| (child_index, depth + 1)),
+                );
+            }
 
-                    let current_parent = self
-                        .parent_index(index_to_move)
-                        .expect("the root should only ever be the first node in the iterator");
+            self.node(index)
+        })
+    }
 
-                    if let Some(mut previous_parent) = previous_parent {
-                        while current_parent <= previous_parent {
-                            new_node = new_node.to_parent().ok().expect(
-                                "the root should only ever be the first node in the iterator",
-                            );
-                            previous_parent = self.parent_index(previous_parent).unwrap();
-                        }
-                    }
+    /// Gets an iterator over the nodes of a binary tree in-order, that is the left subtree of each
+    /// node is returned before the node and its right subtree after it. For a binary search tree
+    /// this returns the nodes in sorted order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree does not have a maximum of 2 children per node.
+    pub fn in_order_iter(&self) -> impl Iterator<Item = Node<'_, N>> {
+        self.require_arity(2);
 
-                    previous_parent = Some(current_parent);
+        let mut pending = Vec::new();
+        let mut next = self.root().map(|root| root.index);
 
-                    let child_offset = index_to_move - self.child_index(current_parent, 0);
-                    new_node = new_node
-                        .to_child_entry(child_offset)
-                        .or_insert(value_to_move);
-                }
+        iter::from_fn(move || {
+            while let Some(index) = next {
+                pending.push(index);
+                next = self.node(self.child_index(index, 0)).map(|left| left.index);
             }
-        }
 
-        new_tree
+            let index = pending.pop()?;
+            next = self
+                .node(self.child_index(index, 1))
+                .map(|right| right.index);
+            self.node(index)
+        })
     }
 
-    fn set_value(&mut self, index: usize, new_value: N) -> NodeMut<N> {
-        self.ensure_size(index);
+    /// Gets an iterator which performs an iterative deepening depth-first search. A pre-order
+    /// depth-first pass is made for each depth limit from 0 up to `max_depth`, each node is
+    /// returned along with its depth.
+    ///
+    /// Nodes are returned again in every pass whose depth limit is at least their depth, so the
+    /// root is returned once for every pass. Passes stop once the depth limit reaches the height
+    /// of the tree as further passes would not visit any more nodes.
+    pub fn iterative_deepening_iter(
+        &self,
+        max_depth: usize,
+    ) -> impl Iterator<Item = (usize, Node<'_, N>)> {
+        let max_depth = self.height().map_or(0, |height| height.min(max_depth));
+        let passes = if self.is_empty() { 0 } else { max_depth + 1 };
 
-        let old_value = mem::replace(&mut self.nodes[index], Some(new_value));
+        (0..passes).flat_map(move |depth_limit| {
+            let mut pending = vec![(0, 0)];
 
-        if old_value.is_none() {
-            self.len += 1;
+            iter::from_fn(move || {
+                let (index, depth) = pending.pop()?;
+
+                if depth < depth_limit {
+                    pending.extend(
+                        self.child_indexes(index)
+                            .rev()
+                            .filter(|&child_index| self.node(child_index).is_some())
+                            .map(|child_index| (child_index, depth + 1)),
+                    );
+                }
+
+                let node = self
+                    .node(index)
+                    .expect("only occupied indexes should be pending");
+                Some((depth, node))
+            })
+        })
+    }
+
+    /// Asserts the tree has exactly the expected nodes, each given by its path of child offsets from
+    /// the root and its value, in any order. Unlike comparing trees this does not depend on how the
+    /// tree was built.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a line for each difference if a node is missing, has a different value or is
+    /// not expected.
+    pub fn assert_shape(&self, expected: &[(Vec<usize>, N)])
+    where
+        N: PartialEq + Debug,
+    {
+        let mut differences = String::new();
+
+        for (path, expected_value) in expected {
+            match self.node_at_path(path) {
+                Some(node) if node.value() == expected_value => {}
+                Some(node) => differences.push_str(&format!(
+                    "\n  {:?}: expected {:?}, found {:?}",
+                    path,
+                    expected_value,
+                    node.value()
+                )),
+                None => differences.push_str(&format!(
+                    "\n  {:?}: expected {:?}, found no node",
+                    path, expected_value
+                )),
+            }
         }
 
-        NodeMut { tree: self, index }
+        for (path, value) in self.iter_paths() {
+            if !expected
+                .iter()
+                .any(|(expected_path, _)| *expected_path == path)
+            {
+                differences.push_str(&format!("\n  {:?}: unexpected {:?}", path, value));
+            }
+        }
+
+        assert!(
+            differences.is_empty(),
+            "the tree does not have the expected shape:{}",
+            differences
+        );
     }
 
-    fn child_index(&self, parent_index: usize, child_offset: usize) -> usize {
-        self.index_calculator
-            .child_index(parent_index, child_offset)
+    /// Gets an iterator over the path of child offsets from the root and the value of each node in
+    /// breadth-first order. Each path may be passed to `node_at_path` to get the node again.
+    pub fn iter_paths(&self) -> impl Iterator<Item = (Vec<usize>, &N)> {
+        self.enumerate_values()
+            .map(move |(index, value)| (self.index_calculator.path(index), value))
     }
 
-    fn parent_index(&self, child_index: usize) -> Option<usize> {
-        self.index_calculator.parent_index(child_index)
+    /// Collects the value of every node into a map keyed by the path of child offsets from the
+    /// root to the node, the same paths as returned by `iter_paths`. The root is keyed by an empty
+    /// path.
+    #[cfg(feature = "std")]
+    pub fn to_path_map(&self) -> HashMap<Vec<usize>, &N> {
+        self.iter_paths().collect()
     }
 
-    fn child_indexes(&self, parent_index: usize) -> Range<usize> {
-        self.index_calculator.child_indexes(parent_index)
+    /// Groups the paths of nodes whose subtrees have the same shape and values, as compared by
+    /// `Node::subtree_eq`. Only groups with more than one path are returned, the groups and the
+    /// paths within them are in breadth-first order. Leaves are subtrees too so leaves with equal
+    /// values are also grouped.
+    #[cfg(feature = "std")]
+    pub fn find_duplicate_subtrees(&self) -> Vec<Vec<Vec<usize>>>
+    where
+        N: Hash + Eq,
+    {
+        // hash each subtree from its value and the hashes of its child slots, children are stored
+        // after their parents so walking the storage backwards hashes every child first
+        let mut hashes = vec![None; self.nodes.len()];
+        for node in self.breadth_first_iter_rev() {
+            let mut hasher = DefaultHasher::new();
+            node.value().hash(&mut hasher);
+            for child_index in self.child_indexes(node.index()) {
+                hashes.get(child_index).copied().flatten().hash(&mut hasher);
+            }
+            hashes[node.index()] = Some(hasher.finish());
+        }
+
+        let mut groups: Vec<Vec<Node<'_, N>>> = vec![];
+        let mut groups_by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+        for node in self.breadth_first_iter_dense() {
+            let hash = hashes[node.index()].expect("every node should have been hashed");
+            let candidates = groups_by_hash.entry(hash).or_default();
+
+            // equal hashes may still be a collision so the subtrees are compared
+            match candidates
+                .iter()
+                .find(|&&group| groups[group][0].subtree_eq(&node))
+            {
+                Some(&group) => groups[group].push(node),
+                None => {
+                    candidates.push(groups.len());
+                    groups.push(vec![node]);
+                }
+            }
+        }
+
+        groups
+            .into_iter()
+            .filter(|group| group.len() > 1)
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|node| self.index_calculator.path(node.index()))
+                    .collect()
+            })
+            .collect()
     }
 
-    fn node(&self, index: usize) -> Option<Node<N>> {
-        if let Some(Some(_)) = self.nodes.get(index) {
-            Some(Node { tree: self, index })
+    /// Calls `f` with the value of each node in breadth-first order, stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by `f`.
+    pub fn try_for_each<E, F>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&N) -> Result<(), E>,
+    {
+        self.breadth_first_iter_dense()
+            .try_for_each(|node| f(node.value()))
+    }
+
+    /// Retains only the nodes for which `f` returns `true`, `f` may also modify each value.
+    /// Nodes are visited in pre-order, when a node is not retained its whole subtree is removed
+    /// without visiting it.
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut N) -> bool,
+    {
+        self.retain_subtrees(vec![0], f);
+    }
+
+    /// Removes every node which is currently a leaf. Nodes which become leaves as a result are not
+    /// removed, so each call peels a single layer from the tree.
+    ///
+    /// # Returns
+    ///
+    /// The number of nodes removed.
+    pub fn trim_leaves(&mut self) -> usize {
+        let leaf_indexes: Vec<_> = self
+            .breadth_first_iter_dense()
+            .filter(|node| node.child_iter().next().is_none())
+            .map(|node| node.index)
+            .collect();
+
+        for &index in &leaf_indexes {
+            self.take_value(index);
+        }
+        self.len -= leaf_indexes.len();
+
+        leaf_indexes.len()
+    }
+
+    /// Calls `f` with the mutable value of each node in breadth-first order.
+    pub fn for_each_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut N),
+    {
+        let nodes = &mut self.nodes;
+
+        for index in self.occupancy.iter() {
+            let value = nodes[index]
+                .as_mut()
+                .expect("a value should exist at an occupied index");
+            f(value);
+        }
+    }
+
+    pub fn into_depth_first_iterator(self, order: DepthFirstOrder) -> DepthFirstIterator<N> {
+        DepthFirstIterator::new(self, order)
+    }
+
+    pub fn into_breadth_first_iterator(self) -> BreadthFirstIterator<N> {
+        BreadthFirstIterator::new(self)
+    }
+
+    /// Converts the tree into pairs of the path of child offsets from the root and the value of
+    /// each node in breadth-first order. The pairs may be passed to `from_paths` to build the tree
+    /// again.
+    pub fn into_path_pairs(self) -> Vec<(Vec<usize>, N)> {
+        let index_calculator = self.index_calculator;
+
+        self.nodes
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, value)| Some((index_calculator.path(index), value?)))
+            .collect()
+    }
+
+    /// Gets whether the Eytzinger tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the number of nodes in the Eytzinger tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Recounts the nodes from the occupied slots of the underlying storage and updates the
+    /// number of nodes returned by `len` to match. This repairs a tree whose stored count has
+    /// become out of sync with its slots.
+    ///
+    /// # Returns
+    ///
+    /// The number of nodes.
+    pub fn recompute_len(&mut self) -> usize {
+        self.len = self.nodes.iter().filter(|node| node.is_some()).count();
+        self.len
+    }
+
+    /// Gets the ratio of nodes to allocated slots, from `0.0` for a tree which only has vacant
+    /// slots to `1.0` for a tree with no vacant slots. An empty tree with no slots has a density
+    /// of `1.0`.
+    pub fn density(&self) -> f64 {
+        if self.nodes.is_empty() {
+            1.0
         } else {
-            None
+            self.len() as f64 / self.nodes.len() as f64
         }
     }
 
-    fn node_mut(&mut self, index: usize) -> Result<NodeMut<N>, &mut Self> {
-        if let Some(Some(_)) = self.nodes.get_mut(index) {
-            Ok(NodeMut {
-                tree: self,
-                index: index,
+    /// Gets the number of allocated slots which are vacant, these are the slots the Eytzinger
+    /// layout requires for positions without a node.
+    pub fn vacant_slot_count(&self) -> usize {
+        self.nodes.len() - self.len
+    }
+
+    /// Gets the height of the tree, this is the number of edges on the longest path from the root to
+    /// a leaf. A tree with only a root has a height of 0.
+    ///
+    /// # Returns
+    ///
+    /// The height of the tree or `None` if the tree is empty.
+    pub fn height(&self) -> Option<usize> {
+        self.root().map(|root| root.height())
+    }
+
+    /// Gets a node at the greatest depth of the tree. If there are multiple nodes at that depth the
+    /// left-most one is returned, this is the first of them in breadth-first order.
+    ///
+    /// # Returns
+    ///
+    /// The deepest node or `None` if the tree is empty.
+    pub fn deepest_node(&self) -> Option<Node<'_, N>> {
+        let last_index = self.occupancy.last()?;
+
+        // every level is stored contiguously so the last node is on the deepest level
+        let depth_indexes = self
+            .index_calculator
+            .depth_indexes(self.index_calculator.depth(last_index));
+        let index = self
+            .occupancy
+            .iter_range(depth_indexes)
+            .next()
+            .expect("the deepest level should contain the last node");
+
+        self.node(index)
+    }
+
+    /// Gets the number of nodes at the specified depth, the root is at depth 0.
+    pub fn count_at_depth(&self, depth: usize) -> usize {
+        self.occupancy
+            .count(self.index_calculator.depth_indexes(depth))
+    }
+
+    /// Gets an iterator over the nodes at the specified depth from left to right, the root is at
+    /// depth 0. Only the slots of that depth are scanned as each depth is stored contiguously.
+    pub fn nodes_at_depth(&self, depth: usize) -> impl Iterator<Item = Node<'_, N>> {
+        self.occupancy
+            .iter_range(self.index_calculator.depth_indexes(depth))
+            .map(move |index| Node { tree: self, index })
+    }
+
+    /// Gets the position of the node in breadth-first order, only counting occupied slots. The root
+    /// has a rank of 0, the ranks of the nodes are contiguous so they may be used to index a `Vec`
+    /// of data for each node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not from this tree.
+    pub fn bfs_rank(&self, node: &Node<'_, N>) -> usize {
+        assert!(
+            ptr::eq(self, node.tree()),
+            "the node should be from this tree"
+        );
+
+        // the flat layout is in breadth-first order so the rank is the number of nodes before it
+        self.occupancy.count(0..node.index)
+    }
+
+    /// Gets the node at the specified position in breadth-first order, this is the inverse of
+    /// `bfs_rank`.
+    ///
+    /// # Returns
+    ///
+    /// The node or `None` if `rank` is not less than `len`.
+    pub fn node_at_bfs_rank(&self, rank: usize) -> Option<Node<'_, N>> {
+        let index = self.occupancy.iter().nth(rank)?;
+        self.node(index)
+    }
+
+    /// Gets the number of nodes with each number of children, the value at index `k` is the number
+    /// of nodes with exactly `k` children. The histogram has `max_children_per_node + 1` entries.
+    pub fn child_count_histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![0; self.max_children_per_node() + 1];
+
+        for node in self.breadth_first_iter_dense() {
+            histogram[node.child_iter().count()] += 1;
+        }
+
+        histogram
+    }
+
+    /// Gets the depth with the most nodes along with the number of nodes at that depth. If multiple
+    /// depths have the same number of nodes the shallowest depth is returned.
+    ///
+    /// # Returns
+    ///
+    /// The depth and number of nodes or `None` if the tree is empty.
+    pub fn widest_level(&self) -> Option<(usize, usize)> {
+        let height = self.height()?;
+
+        (0..=height)
+            .map(|depth| (depth, self.count_at_depth(depth)))
+            .fold(None, |widest, (depth, count)| match widest {
+                Some((_, widest_count)) if widest_count >= count => widest,
+                _ => Some((depth, count)),
             })
-        } else {
-            Err(self)
+    }
+
+    /// Gets whether the tree is height-balanced, that is for every node the heights of its child
+    /// subtrees differ by at most 1. A vacant child slot is treated as having a height of -1.
+    ///
+    /// An empty tree is balanced.
+    pub fn is_balanced(&self) -> bool {
+        match self.root() {
+            Some(root) => self
+                .walk_heights(root, |_, child_heights| {
+                    // heights are offset by one so a vacant child has a height of 0
+                    let offset_heights = child_heights.iter().map(|h| h.map_or(0, |h| h + 1));
+                    let min = offset_heights.clone().min().unwrap_or(0);
+                    let max = offset_heights.max().unwrap_or(0);
+                    max - min <= 1
+                })
+                .is_some(),
+            None => true,
+        }
+    }
+
+    /// Gets the roots of every subtree which is perfect with a height of `depth` in breadth-first
+    /// order, that is every node of the subtree above `depth` has as many children as its level
+    /// allows and every leaf of the subtree is `depth` levels below its root. Leaves are the
+    /// perfect subtrees with a height of 0.
+    pub fn complete_subtrees_of_depth(&self, depth: usize) -> Vec<Node<'_, N>> {
+        let root = match self.root() {
+            Some(root) => root,
+            None => return vec![],
+        };
+
+        // the height of each perfect subtree, children are visited before their parents
+        let mut perfect_heights = vec![None; self.nodes.len()];
+        let mut roots = vec![];
+        for node in root.depth_first_iter(DepthFirstOrder::PostOrder) {
+            // vacant child slots have no height so a node is only perfect if every child is
+            let mut child_heights = self
+                .child_indexes(node.index)
+                .map(|child_index| perfect_heights.get(child_index).copied().flatten());
+
+            let perfect_height = if node.child_iter().next().is_none() {
+                Some(0)
+            } else {
+                child_heights
+                    .next()
+                    .flatten()
+                    .filter(|&height| {
+                        child_heights.all(|child_height| child_height == Some(height))
+                    })
+                    .map(|height| height + 1)
+            };
+
+            if perfect_height == Some(depth) {
+                roots.push(node);
+            }
+            perfect_heights[node.index] = perfect_height;
+        }
+
+        roots.sort_by_key(|node| node.index);
+        roots
+    }
+
+    /// Gets the diameter of the tree, this is the number of edges on the longest path between any
+    /// two nodes. The path does not need to pass through the root. An empty tree or a tree with
+    /// only a root has a diameter of 0.
+    pub fn diameter(&self) -> usize {
+        let root = match self.root() {
+            Some(root) => root,
+            None => return 0,
+        };
+
+        let mut diameter = 0;
+        self.walk_heights(root, |_, child_heights| {
+            // the longest path through a node descends into its two tallest child subtrees
+            let mut tallest = [0; 2];
+            for height in child_heights.iter().flatten().map(|height| height + 1) {
+                if height > tallest[0] {
+                    tallest = [height, tallest[0]];
+                } else if height > tallest[1] {
+                    tallest[1] = height;
+                }
+            }
+
+            diameter = diameter.max(tallest[0] + tallest[1]);
+            true
+        });
+
+        diameter
+    }
+
+    /// Gets whether the tree is complete, that is every level except possibly the last is full and
+    /// the nodes on the last level are as far left as possible.
+    ///
+    /// This is the case when the occupied slots of the underlying storage are contiguous from the
+    /// root. An empty tree is complete.
+    pub fn is_complete(&self) -> bool {
+        self.occupancy.count(0..self.len) == self.len
+    }
+
+    /// Gets whether the tree is perfect, that is every interior node has as many children as its
+    /// level allows and all leaves are at the same depth. An empty tree is perfect.
+    pub fn is_perfect(&self) -> bool {
+        match self.height() {
+            Some(height) => {
+                let slot_count = self.index_calculator.depth_indexes(height).end;
+                let last_index = self.occupancy.last();
+
+                self.len == slot_count && last_index == Some(slot_count - 1)
+            }
+            None => true,
+        }
+    }
+
+    /// Calculates an aggregate for the subtree rooted at every node in a single bottom-up pass.
+    ///
+    /// `f` is called for each node with its value and the aggregates of its occupied children in
+    /// child order, children are always aggregated before their parent.
+    ///
+    /// # Returns
+    ///
+    /// The aggregates indexed by the position of each node in the underlying storage, vacant
+    /// slots have no aggregate.
+    pub fn subtree_aggregates<B, F>(&self, mut f: F) -> Vec<Option<B>>
+    where
+        F: FnMut(&N, &[B]) -> B,
+    {
+        let mut aggregates: Vec<Option<B>> = (0..self.nodes.len()).map(|_| None).collect();
+        let mut child_aggregates = Vec::with_capacity(self.max_children_per_node());
+
+        // children are always stored after their parents so walking the storage backwards
+        // aggregates every child before its parent
+        for node in self.breadth_first_iter_rev() {
+            let index = node.index();
+            let child_indexes = self.child_indexes(index);
+
+            child_aggregates.extend(
+                child_indexes
+                    .clone()
+                    .filter_map(|child_index| aggregates.get_mut(child_index)?.take()),
+            );
+
+            let aggregate = f(node.value(), &child_aggregates);
+
+            // put the child aggregates back as they are also returned
+            let mut taken = child_aggregates.drain(..);
+            for child_index in child_indexes {
+                if self.node(child_index).is_some() {
+                    aggregates[child_index] = taken.next();
+                }
+            }
+
+            aggregates[index] = Some(aggregate);
+        }
+
+        aggregates
+    }
+
+    /// Gets the maximum number of children per parent node. For a tree created with `mixed_arity`
+    /// this is the largest arity of any level.
+    pub fn max_children_per_node(&self) -> usize {
+        self.index_calculator.max_children_per_node()
+    }
+
+    /// Changes the maximum number of children per parent node. This is only allowed while the tree
+    /// is empty or only has a root, as the position of every other node depends on the arity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_arity` is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ArityChangeError::HasChildren` if the tree has nodes other than the root.
+    pub fn set_arity(&mut self, new_arity: usize) -> Result<(), ArityChangeError> {
+        if self.len > 1 {
+            return Err(ArityChangeError::HasChildren);
+        }
+
+        self.index_calculator = EytzingerIndexCalculator::new(new_arity);
+        self.truncate_unused();
+        Ok(())
+    }
+
+    /// Gets the flat storage index of the child at the specified offset of the node at the
+    /// specified flat storage index, this is `parent * max_children_per_node + child + 1` unless the
+    /// tree was created with `mixed_arity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is not less than `max_children_per_node` or the index exceeds the
+    /// capacity of the tree.
+    pub fn child_flat_index(&self, parent: usize, child: usize) -> usize {
+        self.child_index(parent, child)
+    }
+
+    /// Gets the flat storage index of the parent of the node at the specified flat storage index,
+    /// `None` for the root at index 0.
+    pub fn parent_flat_index(&self, child: usize) -> Option<usize> {
+        self.parent_index(child)
+    }
+
+    /// Gets the range of flat storage indexes for each depth from the root down to `up_to_depth`,
+    /// the nodes at each depth are stored contiguously in the range at the same position. Ranges
+    /// saturate at `usize::MAX` for depths which cannot be addressed.
+    pub fn depth_slot_ranges(&self, up_to_depth: usize) -> Vec<Range<usize>> {
+        (0..=up_to_depth)
+            .map(|depth| self.index_calculator.depth_indexes(depth))
+            .collect()
+    }
+
+    /// Gets the value of the node at the specified flat storage index, `None` if the index is out
+    /// of range or the slot is vacant.
+    pub fn get(&self, flat_index: usize) -> Option<&N> {
+        self.nodes.get(flat_index).and_then(Option::as_ref)
+    }
+
+    /// Gets the mutable value of the node at the specified flat storage index, `None` if the index
+    /// is out of range or the slot is vacant.
+    pub fn get_mut(&mut self, flat_index: usize) -> Option<&mut N> {
+        self.nodes.get_mut(flat_index).and_then(Option::as_mut)
+    }
+
+    /// Gets an iterator over the value of each occupied slot along with its flat storage index,
+    /// the values are returned in ascending flat index order and vacant slots are skipped. The
+    /// flat indexes are those accepted by `get` and `get_mut`.
+    pub fn enumerate_slots(&self) -> impl Iterator<Item = (usize, &N)> {
+        self.enumerate_values()
+    }
+
+    /// Swaps the contents of this tree with `other` without copying any nodes. The trees may have
+    /// different arities, each tree takes the arity of the other along with its nodes.
+    pub fn swap(&mut self, other: &mut Self) {
+        mem::swap(self, other);
+    }
+
+    /// Clears the Eytzinger tree, removing all nodes. Every value is dropped, vacant slots hold no
+    /// values so nothing is dropped for them.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.occupancy.clear();
+        self.len = 0;
+    }
+
+    /// Consumes the Eytzinger tree, dropping the values in post-order so the values of children
+    /// are always dropped before the value of their parent. Dropping the tree normally drops the
+    /// values in an unspecified order.
+    pub fn drop_post_order(mut self) {
+        let indexes: Vec<_> = self
+            .depth_first_iter(DepthFirstOrder::PostOrder)
+            .map(|node| node.index)
+            .collect();
+
+        for index in indexes {
+            drop(self.nodes[index].take());
         }
     }
 
-    fn entry(&mut self, index: usize) -> Entry<N> {
-        match self.node_mut(index) {
-            Ok(node) => Entry::Occupied(node),
-            Err(tree) => Entry::Vacant(VacantEntry { tree, index }),
-        }
+    /// Gets the root node, `None` if there was no root node.
+    ///
+    /// The root node may be set with `set_root_value`.
+    pub fn root(&self) -> Option<Node<'_, N>> {
+        self.node(0)
+    }
+
+    /// Gets the node at the specified path of child offsets from the root, an empty path is the
+    /// root.
+    ///
+    /// # Returns
+    ///
+    /// The node or `None` if there was no node at the path.
+    pub fn node_at_path(&self, path: &[usize]) -> Option<Node<'_, N>> {
+        self.node(self.path_index(path)?)
+    }
+
+    /// Gets every node whose value matches the predicate in breadth-first order. The nodes borrow
+    /// the tree, `find_path` may be used instead to get an owned path to the first match.
+    pub fn find_all<F>(&self, mut f: F) -> Vec<Node<'_, N>>
+    where
+        F: FnMut(&N) -> bool,
+    {
+        self.breadth_first_iter_dense()
+            .filter(|node| f(node.value()))
+            .collect()
+    }
+
+    /// Gets the path of child offsets from the root to the first node, in breadth-first order,
+    /// whose value matches the predicate. Unlike a `Node` the path does not borrow the tree so it
+    /// may be used to mutate the tree afterwards.
+    pub fn find_path<F>(&self, mut f: F) -> Option<Vec<usize>>
+    where
+        F: FnMut(&N) -> bool,
+    {
+        self.breadth_first_iter()
+            .find(|node| f(node.value()))
+            .map(|node| self.index_calculator.path(node.index))
+    }
+
+    /// Gets the path of child offsets from the root to the first node, in breadth-first order,
+    /// at which `other` is found. `other` is found at a node when each of its nodes has a node
+    /// with an equal value at the same position below that node, further descendants of the node
+    /// are allowed. An empty `other` is found at the root of any tree.
+    pub fn contains_subtree(&self, other: &EytzingerTree<N>) -> Option<Vec<usize>>
+    where
+        N: PartialEq,
+    {
+        let other_root = match other.root() {
+            Some(other_root) => other_root,
+            None => return Some(vec![]),
+        };
+
+        self.breadth_first_iter()
+            .find(|node| node.subtree_matches(&other_root))
+            .map(|node| self.index_calculator.path(node.index))
+    }
+
+    /// Gets a read-only cursor which starts at the root node.
+    pub fn cursor(&self) -> Cursor<'_, N> {
+        Cursor::new(self)
+    }
+
+    /// Gets the mutable root node, `None` if there was no root node.
+    ///
+    /// The root node may be set with `set_root_value`.
+    pub fn root_mut(&mut self) -> Option<NodeMut<'_, N>> {
+        self.node_mut(0).ok()
+    }
+
+    /// Sets the value of the root node. All child nodes will remain as they are, any existing root
+    /// value is dropped. `remove_root_value` removes the root along with all child nodes.
+    ///
+    /// # Returns
+    ///
+    /// The new root node.
+    pub fn set_root_value(&mut self, new_value: N) -> NodeMut<'_, N> {
+        self.set_value(0, new_value)
+    }
+
+    /// Calls `f` with the mutable value of the root node so it may be updated in place, the
+    /// structure of the tree is left as it is.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there was a root node, `false` otherwise.
+    pub fn update_root<F>(&mut self, f: F) -> bool
+    where
+        F: FnOnce(&mut N),
+    {
+        match self.get_mut(0) {
+            Some(value) => {
+                f(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the value of the root node only if the tree has no root, this prevents accidentally
+    /// replacing the root of an existing tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RootOccupied` containing `new_value` if the tree already has a root, the existing
+    /// root is left as it is.
+    ///
+    /// # Returns
+    ///
+    /// The new root node.
+    pub fn try_set_root_value(&mut self, new_value: N) -> Result<NodeMut<'_, N>, RootOccupied<N>> {
+        if self.occupancy.contains(0) {
+            Err(RootOccupied { value: new_value })
+        } else {
+            Ok(self.set_value(0, new_value))
+        }
+    }
+
+    /// Removes the root value. This will also remove all children.
+    ///
+    /// # Returns
+    ///
+    /// The old root value if there was one.
+    pub fn remove_root_value(&mut self) -> (Option<N>, VacantEntry<'_, N>) {
+        self.nodes.truncate(1);
+        self.len = 0;
+        let value = self.take_value(0);
+        self.occupancy.clear();
+
+        (
+            value,
+            VacantEntry {
+                tree: self,
+                index: 0,
+            },
+        )
+    }
+
+    /// Gets the entry for the root node.
+    ///
+    /// # Examples
+    ///
+    /// ```    
+    /// use lz_eytzinger_tree::{EytzingerTree, entry::Entry};
+    ///
+    /// let tree = {
+    ///     let mut tree = EytzingerTree::<u32>::new(8);
+    ///     tree.root_entry().or_insert(5);
+    ///     tree
+    /// };
+    ///
+    /// let root = tree.root().unwrap();
+    /// assert_eq!(root.value(), &5);
+    /// ```
+    pub fn root_entry(&mut self) -> Entry<'_, N> {
+        self.entry_at(0)
+    }
+
+    /// Gets the read-only entry at the specified path of child offsets from the root, an empty path
+    /// is the root. The entry is vacant when there is no node at the path but there is one for its
+    /// parent.
+    ///
+    /// # Returns
+    ///
+    /// The entry or `None` if a child offset is out of range or there is no node for the parent
+    /// of the path. Such a path has no slot a value could be inserted into so it is not treated as
+    /// vacant.
+    pub fn entry(&self, path: &[usize]) -> Option<EntryRef<'_, N>> {
+        let index = self.path_index(path)?;
+
+        match (self.node(index), self.parent_index(index)) {
+            (Some(node), _) => Some(EntryRef::Occupied(node)),
+            (None, Some(parent_index)) if self.node(parent_index).is_none() => None,
+            (None, _) => Some(EntryRef::Vacant(VacantEntryRef { tree: self, index })),
+        }
+    }
+
+    /// Gets the entry at the specified path of child offsets from the root, an empty path is the
+    /// root. The entry is vacant when there is no node at the path but there is one for its
+    /// parent, so a value may be inserted. `entry` may be used to get the entry without requiring
+    /// mutable access.
+    ///
+    /// # Returns
+    ///
+    /// The entry or `None` if a child offset is out of range or there is no node for the parent
+    /// of the path. Such a path has no slot a value could be inserted into so it is not treated as
+    /// vacant.
+    pub fn entry_mut(&mut self, path: &[usize]) -> Option<Entry<'_, N>> {
+        let index = self.path_index(path)?;
+
+        match self.parent_index(index) {
+            Some(parent_index) if self.node(parent_index).is_none() => None,
+            _ => Some(self.entry_at(index)),
+        }
+    }
+
+    /// Gets mutable references to the values at each of the specified paths of child offsets from
+    /// the root, in the same order as the paths. This allows several values to be updated at once,
+    /// such as a parent and its children.
+    ///
+    /// # Returns
+    ///
+    /// The mutable values or `None` if there is no node for a path or the same node is specified
+    /// more than once.
+    pub fn get_disjoint_mut(&mut self, paths: &[&[usize]]) -> Option<Vec<&mut N>> {
+        let indexes = paths
+            .iter()
+            .map(|path| {
+                self.path_index(path)
+                    .filter(|&index| self.node(index).is_some())
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut sorted_indexes = indexes.clone();
+        sorted_indexes.sort_unstable();
+        if sorted_indexes.windows(2).any(|pair| pair[0] == pair[1]) {
+            return None;
+        }
+
+        // split the storage after each index in ascending order so each value is borrowed from
+        // a separate slice
+        let mut sorted_values = Vec::with_capacity(sorted_indexes.len());
+        let mut remaining = &mut self.nodes[..];
+        let mut offset = 0;
+        for &index in &sorted_indexes {
+            let (head, tail) = mem::take(&mut remaining).split_at_mut(index + 1 - offset);
+            sorted_values.push(head.last_mut().and_then(Option::as_mut));
+            remaining = tail;
+            offset = index + 1;
+        }
+
+        let values = indexes
+            .iter()
+            .map(|index| {
+                let position = sorted_indexes
+                    .binary_search(index)
+                    .expect("the index should have been sorted");
+                sorted_values[position]
+                    .take()
+                    .expect("a value should exist at an occupied index")
+            })
+            .collect();
+        Some(values)
+    }
+
+    /// Builds a new `EytzingerTree<N>` with the values mapped
+    /// using the specified selector.
+    pub fn map<U, F>(self, mut f: F) -> EytzingerTree<U>
+    where
+        F: FnMut(N) -> U,
+    {
+        let nodes = self.nodes.into_iter().map(|n| n.map(&mut f)).collect();
+
+        EytzingerTree {
+            nodes,
+            occupancy: self.occupancy,
+            index_calculator: self.index_calculator,
+            len: self.len,
+        }
+    }
+
+    /// Copies the subtree at the `from` path and attaches the copy as the child at index `child` of
+    /// the node at the `to_parent` path. The original subtree is left in place.
+    ///
+    /// Paths are the child offsets to follow from the root, an empty path is the root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is not less than `max_children_per_node`, or for a tree created with
+    /// `mixed_arity` if a copied node would have more children than its new level allows.
+    ///
+    /// # Errors
+    ///
+    /// * `CopyError::VacantSource` if there is no node at `from`.
+    /// * `CopyError::VacantDestinationParent` if there is no node at `to_parent`.
+    /// * `CopyError::OccupiedDestination` if there is already a node at the destination.
+    /// * `CopyError::DestinationInSource` if the destination is within the source subtree.
+    pub fn copy_subtree(
+        &mut self,
+        from: &[usize],
+        to_parent: &[usize],
+        child: usize,
+    ) -> Result<(), CopyError>
+    where
+        N: Clone,
+    {
+        self.copy_subtree_with(from, to_parent, child, OverwritePolicy::Error)
+    }
+
+    /// Copies the subtree at the `from` path and attaches the copy as the child at index `child` of
+    /// the node at the `to_parent` path, `policy` determines what happens when there is already a
+    /// node at the destination. The original subtree is left in place.
+    ///
+    /// Paths are the child offsets to follow from the root, an empty path is the root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is not less than `max_children_per_node`, or for a tree created with
+    /// `mixed_arity` if a copied node would have more children than its new level allows.
+    ///
+    /// # Errors
+    ///
+    /// * `CopyError::VacantSource` if there is no node at `from`.
+    /// * `CopyError::VacantDestinationParent` if there is no node at `to_parent`.
+    /// * `CopyError::OccupiedDestination` if there is already a node at the destination and the
+    ///   policy is `OverwritePolicy::Error`.
+    /// * `CopyError::Collision` if the policy is `OverwritePolicy::Merge` and a copied node would
+    ///   be placed at an occupied slot below the destination.
+    /// * `CopyError::DestinationInSource` if the destination is the source or within it.
+    pub fn copy_subtree_with(
+        &mut self,
+        from: &[usize],
+        to_parent: &[usize],
+        child: usize,
+        policy: OverwritePolicy,
+    ) -> Result<(), CopyError>
+    where
+        N: Clone,
+    {
+        let source_index = self
+            .path_index(from)
+            .filter(|&index| self.node(index).is_some())
+            .ok_or(CopyError::VacantSource)?;
+
+        let parent_index = self
+            .path_index(to_parent)
+            .filter(|&index| self.node(index).is_some())
+            .ok_or(CopyError::VacantDestinationParent)?;
+
+        let destination_index = self.child_index(parent_index, child);
+        let destination_occupied = self.node(destination_index).is_some();
+        if destination_occupied && policy == OverwritePolicy::Error {
+            return Err(CopyError::OccupiedDestination);
+        }
+
+        let mut ancestor_index = Some(destination_index);
+        while let Some(index) = ancestor_index {
+            if index == source_index {
+                return Err(CopyError::DestinationInSource);
+            }
+            ancestor_index = self.parent_index(index);
+        }
+
+        // clone everything up front as the destination may grow the storage
+        let mut copies = vec![];
+        let mut pending = vec![(source_index, destination_index)];
+        while let Some((source_index, destination_index)) = pending.pop() {
+            if let Some(Some(value)) = self.nodes.get(source_index) {
+                copies.push((destination_index, value.clone()));
+
+                for (offset, child_index) in self.child_indexes(source_index).enumerate() {
+                    if self.node(child_index).is_some() {
+                        pending.push((child_index, self.child_index(destination_index, offset)));
+                    }
+                }
+            }
+        }
+
+        if destination_occupied {
+            match policy {
+                OverwritePolicy::Error => unreachable!("the destination was checked to be vacant"),
+                OverwritePolicy::Overwrite => {
+                    self.remove(destination_index);
+                }
+                OverwritePolicy::Merge => {
+                    // the source root is copied first, the node at the destination is kept instead
+                    copies.remove(0);
+                    if copies.iter().any(|&(index, _)| self.node(index).is_some()) {
+                        return Err(CopyError::Collision);
+                    }
+                }
+            }
+        }
+
+        for (index, value) in copies {
+            self.set_value(index, value);
+        }
+
+        Ok(())
+    }
+
+    /// Swaps the values of the nodes at the paths `a` and `b`. Only the values are swapped, the
+    /// children of each node are left where they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SwapError::Vacant` if there is no node at either path.
+    pub fn swap_values(&mut self, a: &[usize], b: &[usize]) -> Result<(), SwapError> {
+        let occupied_index = |path: &[usize]| {
+            self.path_index(path)
+                .filter(|&index| self.node(index).is_some())
+                .ok_or_else(|| SwapError::Vacant {
+                    path: path.to_vec(),
+                })
+        };
+
+        let a_index = occupied_index(a)?;
+        let b_index = occupied_index(b)?;
+
+        self.nodes.swap(a_index, b_index);
+
+        Ok(())
+    }
+
+    /// Removes the node at the specified path without removing its children, the children are
+    /// moved up to take vacant child slots of the node's parent along with their subtrees. The
+    /// first child takes the slot of the removed node and any other children take the vacant
+    /// slots of the parent in order.
+    ///
+    /// # Errors
+    ///
+    /// * `SpliceError::Vacant` if there is no node at the path.
+    /// * `SpliceError::IsRoot` if the path is the root.
+    /// * `SpliceError::InsufficientSlots` if the parent does not have enough vacant child slots.
+    /// * `SpliceError::ArityMismatch` if the tree was created with `mixed_arity` and the children
+    ///   would move to a level with a different arity.
+    ///
+    /// # Returns
+    ///
+    /// The value of the removed node.
+    pub fn splice_out(&mut self, path: &[usize]) -> Result<N, SpliceError> {
+        let index = self
+            .path_index(path)
+            .filter(|&index| self.node(index).is_some())
+            .ok_or_else(|| SpliceError::Vacant {
+                path: path.to_vec(),
+            })?;
+        let parent_index = self.parent_index(index).ok_or(SpliceError::IsRoot)?;
+
+        let child_indexes: Vec<_> = self
+            .child_indexes(index)
+            .filter(|&child_index| self.node(child_index).is_some())
+            .collect();
+
+        // the slot of the node is used first followed by the vacant slots of the parent
+        let slots: Vec<_> = iter::once(index)
+            .chain(
+                self.child_indexes(parent_index)
+                    .filter(|&slot| self.node(slot).is_none()),
+            )
+            .take(child_indexes.len())
+            .collect();
+        if slots.len() < child_indexes.len() {
+            return Err(SpliceError::InsufficientSlots);
+        }
+
+        if let Some(&child_index) = child_indexes.first() {
+            if self.index_calculator.subtree(child_index) != self.index_calculator.subtree(index) {
+                return Err(SpliceError::ArityMismatch);
+            }
+        }
+
+        let subtrees: Vec<_> = child_indexes
+            .into_iter()
+            .map(|child_index| self.split_off_index(child_index))
+            .collect();
+
+        let value = self
+            .remove(index)
+            .expect("there should be a value at the index");
+
+        for (slot, subtree) in slots.into_iter().zip(subtrees) {
+            self.graft(slot, subtree);
+        }
+
+        Ok(value)
+    }
+
+    /// Moves the value of the node at the specified path towards the root, swapping it with the
+    /// value of its parent while it is greater, to restore the max-heap property. Only values are
+    /// swapped, the shape of the tree is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_children_per_node` is not 2, the tree is not complete or there is no node at
+    /// the path.
+    pub fn sift_up(&mut self, path: &[usize])
+    where
+        N: Ord,
+    {
+        let mut index = self.heap_index(path);
+
+        while let Some(parent_index) = self.parent_index(index) {
+            // every slot of a complete tree is occupied so comparing the slots compares the values
+            if self.nodes[index] <= self.nodes[parent_index] {
+                break;
+            }
+
+            self.nodes.swap(index, parent_index);
+            index = parent_index;
+        }
+    }
+
+    /// Moves the value of the node at the specified path away from the root, swapping it with the
+    /// value of its greatest child while that is greater, to restore the max-heap property. Only
+    /// values are swapped, the shape of the tree is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_children_per_node` is not 2, the tree is not complete or there is no node at
+    /// the path.
+    pub fn sift_down(&mut self, path: &[usize])
+    where
+        N: Ord,
+    {
+        let mut index = self.heap_index(path);
+
+        loop {
+            let greatest_child_index = self
+                .child_indexes(index)
+                .filter(|&child_index| child_index < self.len)
+                .max_by(|&a, &b| self.nodes[a].cmp(&self.nodes[b]));
+
+            match greatest_child_index {
+                Some(child_index) if self.nodes[child_index] > self.nodes[index] => {
+                    self.nodes.swap(index, child_index);
+                    index = child_index;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Cyclically shifts the children of every node by one, so the child at each index moves to the
+    /// next index and the last child moves to index 0. The subtrees move with their roots, so the
+    /// tree is restored after being cycled `max_children_per_node` times.
+    pub fn cycle_children(&mut self) {
+        let mut cycled = Self::with_index_calculator(self.index_calculator.clone());
+        let mut cycled_indexes = vec![0; self.nodes.len()];
+
+        // parents are stored before their children so each parent has moved before its children
+        for index in self.occupancy.iter() {
+            let cycled_index = match self.index_calculator.parent_index(index) {
+                Some(parent_index) => {
+                    let offset = index - self.index_calculator.child_index(parent_index, 0);
+                    let child_count = self.index_calculator.child_count(parent_index);
+
+                    self.index_calculator
+                        .child_index(cycled_indexes[parent_index], (offset + 1) % child_count)
+                }
+                None => index,
+            };
+
+            let value = self.nodes[index]
+                .take()
+                .expect("a value should exist at an occupied index");
+            cycled.set_value(cycled_index, value);
+            cycled_indexes[index] = cycled_index;
+        }
+
+        *self = cycled;
+    }
+
+    /// Removes the subtree at the specified path of child offsets from the root and returns it as
+    /// a new tree with the same maximum number of children per node, or for a tree created with
+    /// `mixed_arity` the arities of the levels from the subtree downwards. The slot the subtree was
+    /// removed from is left vacant.
+    ///
+    /// # Returns
+    ///
+    /// The removed subtree or `None` if there was no node at the path.
+    pub fn split_off(&mut self, path: &[usize]) -> Option<EytzingerTree<N>> {
+        let index = self
+            .path_index(path)
+            .filter(|&index| self.node(index).is_some())?;
+
+        Some(self.split_off_index(index))
+    }
+
+    /// Replaces the subtree at the specified path of child offsets from the root with `new_subtree`
+    /// and returns the old subtree as a new tree. If `new_subtree` is empty the slot is left
+    /// vacant.
+    ///
+    /// # Errors
+    ///
+    /// * `ReplaceError::Vacant` if there is no node at `path`.
+    /// * `ReplaceError::ArityMismatch` if `new_subtree` does not have the same number of children
+    ///   per node as the subtree at `path`.
+    pub fn replace_subtree(
+        &mut self,
+        path: &[usize],
+        new_subtree: EytzingerTree<N>,
+    ) -> Result<EytzingerTree<N>, ReplaceError> {
+        let index = self
+            .path_index(path)
+            .filter(|&index| self.node(index).is_some())
+            .ok_or_else(|| ReplaceError::Vacant {
+                path: path.to_vec(),
+            })?;
+
+        if new_subtree.index_calculator != self.index_calculator.subtree(index) {
+            return Err(ReplaceError::ArityMismatch);
+        }
+
+        let old_subtree = self.split_off_index(index);
+        self.graft(index, new_subtree);
+
+        Ok(old_subtree)
+    }
+
+    /// Rebalances a binary search tree so it is height-balanced. The in-order sequence of values is
+    /// preserved, the tree is rebuilt by repeatedly choosing the median of the in-order values
+    /// as the root of each subtree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_children_per_node` is not 2.
+    pub fn balance_bst(&mut self)
+    where
+        N: Ord,
+    {
+        self.require_arity(2);
+
+        let mut values: Vec<_> = self
+            .in_order_indexes()
+            .into_iter()
+            .map(|index| self.take_value(index))
+            .collect();
+
+        self.nodes.clear();
+        self.occupancy.clear();
+        self.len = 0;
+
+        if values.is_empty() {
+            return;
+        }
+
+        let mut pending = vec![(0, 0..values.len())];
+        while let Some((index, range)) = pending.pop() {
+            let median = range.start + (range.end - range.start) / 2;
+
+            let value = values[median]
+                .take()
+                .expect("each value should only be taken once");
+            self.set_value(index, value);
+
+            if range.start < median {
+                pending.push((self.child_index(index, 0), range.start..median));
+            }
+            if median + 1 < range.end {
+                pending.push((self.child_index(index, 1), median + 1..range.end));
+            }
+        }
+    }
+
+    /// Gets the values of a binary search tree in sorted order.
+    ///
+    /// The values are returned in in-order sequence, left subtree then node then right subtree,
+    /// which is sorted only if the tree holds the binary search tree invariant. The values are
+    /// not re-sorted, see `is_valid_bst` to check the invariant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_children_per_node` is not 2.
+    pub fn to_sorted_vec(&self) -> Vec<N>
+    where
+        N: Clone,
+    {
+        self.require_arity(2);
+
+        self.in_order_indexes()
+            .into_iter()
+            .map(|index| {
+                self.nodes[index]
+                    .clone()
+                    .expect("a value should exist at the index")
+            })
+            .collect()
+    }
+
+    /// Gets whether a binary tree is a valid binary search tree, that is an in-order traversal
+    /// returns strictly increasing values. An empty tree is a valid binary search tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_children_per_node` is not 2.
+    pub fn is_valid_bst(&self) -> bool
+    where
+        N: Ord,
+    {
+        let mut previous: Option<&N> = None;
+
+        self.in_order_iter().all(|node| {
+            let value = node.value();
+            let increasing = match previous {
+                Some(previous) => previous < value,
+                None => true,
+            };
+            previous = Some(value);
+            increasing
+        })
+    }
+
+    /// Gets the number of slots the inner storage can hold without reallocating. Each slot may
+    /// hold a node, this includes vacant slots.
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Reserves storage for a complete tree down to `depth`, the root being at depth 0, so filling
+    /// the tree to that depth does not reallocate.
+    ///
+    /// For `m` children per node a complete tree down to `depth` takes `(m^(depth+1)-1)/(m-1)`
+    /// slots, or `depth + 1` slots when `m` is 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of slots cannot be represented by a `usize` or the storage exceeds
+    /// `isize::MAX` bytes, as for `Vec::reserve`.
+    pub fn reserve_depth(&mut self, depth: usize) {
+        let slot_count = self.index_calculator.depth_indexes(depth).end;
+
+        if let Some(additional) = slot_count.checked_sub(self.nodes.len()) {
+            self.nodes.reserve(additional);
+        }
+    }
+
+    /// Shrinks the inner storage of the tree to only take up as much space as required. Vacant
+    /// slots after the last node are removed, as done by `truncate_unused`, and any spare capacity
+    /// is released.
+    pub fn shrink_to_fit(&mut self) {
+        self.truncate_unused();
+        self.occupancy.truncate(self.nodes.len());
+        self.nodes.shrink_to_fit();
+    }
+
+    /// Clones the tree into the minimum storage required. Unlike `clone` the copy does not
+    /// include any vacant slots after the last node or any spare capacity.
+    pub fn compact_clone(&self) -> Self
+    where
+        N: Clone,
+    {
+        let used_len = self.occupancy.last().map_or(0, |last| last + 1);
+
+        let mut nodes = Vec::with_capacity(used_len);
+        nodes.extend_from_slice(&self.nodes[..used_len]);
+
+        Self {
+            nodes,
+            occupancy: self.occupancy.clone(),
+            index_calculator: self.index_calculator.clone(),
+            len: self.len,
+        }
+    }
+
+    /// Removes any vacant slots after the last node from the inner storage and returns the number
+    /// of slots removed. Unlike `shrink_to_fit` the capacity of the storage is kept so it may be
+    /// reused by later insertions.
+    pub fn truncate_unused(&mut self) -> usize {
+        let used_len = self.occupancy.last().map_or(0, |last| last + 1);
+        let removed = self.nodes.len() - used_len;
+
+        self.nodes.truncate(used_len);
+        removed
+    }
+
+    fn with_index_calculator(index_calculator: EytzingerIndexCalculator) -> Self {
+        Self {
+            nodes: vec![],
+            occupancy: Occupancy::new(),
+            index_calculator,
+            len: 0,
+        }
+    }
+
+    /// Gets an iterator over each value and its index in the tree.
+    fn enumerate_values(&self) -> impl Iterator<Item = (usize, &N)> {
+        self.occupancy.iter().map(move |index| {
+            let value = self.nodes[index]
+                .as_ref()
+                .expect("a value should exist at an occupied index");
+            (index, value)
+        })
+    }
+
+    /// Gets the index of the node at the path for the heap operations, asserting the tree is a
+    /// complete binary tree.
+    fn heap_index(&self, path: &[usize]) -> usize {
+        self.require_arity(2);
+        assert!(self.is_complete(), "operation requires a complete tree");
+
+        self.path_index(path)
+            .filter(|&index| self.node(index).is_some())
+            .expect("there should be a node at the path")
+    }
+
+    /// Asserts the tree has the expected maximum number of children per node, this is used by
+    /// operations which only make sense for a specific arity such as binary trees.
+    fn require_arity(&self, expected: usize) {
+        assert!(
+            self.index_calculator.is_uniform(),
+            "operation requires the same arity for every level"
+        );
+
+        let actual = self.max_children_per_node();
+        assert!(
+            actual == expected,
+            "operation requires max_children_per_node == {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    /// Gets the indexes of all nodes of a binary tree in left-node-right order.
+    fn in_order_indexes(&self) -> Vec<usize> {
+        let mut indexes = Vec::with_capacity(self.len);
+        let mut pending = vec![];
+        let mut current = 0;
+
+        loop {
+            while self.node(current).is_some() {
+                pending.push(current);
+                current = self.child_index(current, 0);
+            }
+
+            match pending.pop() {
+                Some(index) => {
+                    indexes.push(index);
+                    current = self.child_index(index, 1);
+                }
+                None => return indexes,
+            }
+        }
+    }
+
+    /// Walks the subtree starting at `node` in post-order calculating the height of each node.
+    /// `visit` is called for each node with the heights of its child slots (`None` for vacant
+    /// slots), the walk stops early if `visit` returns `false`.
+    ///
+    /// # Returns
+    ///
+    /// The height of `node` or `None` if the walk was stopped early.
+    fn walk_heights<F>(&self, node: Node<'_, N>, mut visit: F) -> Option<usize>
+    where
+        F: FnMut(Node<'_, N>, &[Option<usize>]) -> bool,
+    {
+        let mut heights = vec![None; self.nodes.len()];
+        let mut child_heights = Vec::with_capacity(self.max_children_per_node());
+
+        for current in node.depth_first_iter(DepthFirstOrder::PostOrder) {
+            child_heights.clear();
+            child_heights.extend(
+                self.child_indexes(current.index())
+                    .map(|child_index| heights.get(child_index).cloned().flatten()),
+            );
+
+            if !visit(current, &child_heights) {
+                return None;
+            }
+
+            let height = child_heights
+                .iter()
+                .flatten()
+                .max()
+                .map_or(0, |child_height| child_height + 1);
+
+            heights[current.index()] = Some(height);
+        }
+
+        heights[node.index()]
+    }
+
+    fn set_child_value(&mut self, parent: usize, child: usize, new_value: N) -> NodeMut<'_, N> {
+        let child_index = self.child_index(parent, child);
+        self.set_value(child_index, new_value)
+    }
+
+    fn try_set_child_value(
+        &mut self,
+        parent: usize,
+        child: usize,
+        new_value: N,
+    ) -> Result<NodeMut<'_, N>, CapacityError> {
+        let child_index = self.index_calculator.try_child_index(parent, child)?;
+        self.try_set_value(child_index, new_value)
+    }
+
+    /// Sets the value at the specified index, `Err(CapacityError)` if the storage for the index
+    /// could not be allocated.
+    fn try_set_value(
+        &mut self,
+        index: usize,
+        new_value: N,
+    ) -> Result<NodeMut<'_, N>, CapacityError> {
+        self.try_reserve_index(index)?;
+        Ok(self.set_value(index, new_value))
+    }
+
+    /// Reserves storage so a value may be set at the specified index without reallocating,
+    /// `Err(CapacityError)` if the storage could not be allocated.
+    fn try_reserve_index(&mut self, index: usize) -> Result<(), CapacityError> {
+        let desired_len = index.checked_add(1).ok_or(CapacityError)?;
+        if let Some(additional) = desired_len.checked_sub(self.nodes.len()) {
+            self.nodes
+                .try_reserve(additional)
+                .map_err(|_| CapacityError)?;
+        }
+
+        Ok(())
+    }
+
+    fn ensure_size(&mut self, index: usize) {
+        let desired_len = index.checked_add(1).expect("index overflow");
+
+        if desired_len > self.nodes.len() {
+            trace!(
+                "growing storage from {} to {} slots",
+                self.nodes.len(),
+                desired_len
+            );
+
+            self.nodes.resize_with(desired_len, || None);
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> Option<N> {
+        if let Some(None) | None = self.nodes.get(index) {
+            return None;
+        }
+
+        // walk the subtree in post-order clearing each slot in place, this avoids both recursion
+        // and collecting the indices to remove up front
+        let mut removed_count = 0;
+        let mut current = index;
+        loop {
+            let first_child = self
+                .child_indexes(current)
+                .find(|&child_index| self.node(child_index).is_some());
+
+            if let Some(first_child) = first_child {
+                current = first_child;
+                continue;
+            }
+
+            let removed_value = self.take_value(current);
+            self.len -= 1;
+            removed_count += 1;
+
+            if current == index {
+                debug!(
+                    "removed {} nodes from the subtree at index {}",
+                    removed_count, index
+                );
+                return removed_value;
+            }
+
+            current = self
+                .parent_index(current)
+                .expect("only the starting node may be the root");
+        }
+    }
+
+    /// Retains only the descendants of the node at the specified index for which `f` returns
+    /// `true`, the node itself is not passed to `f`. Descendants are visited in pre-order, when a
+    /// descendant is not retained its whole subtree is removed without visiting it.
+    fn retain_descendants<F>(&mut self, index: usize, f: F)
+    where
+        F: FnMut(&mut N) -> bool,
+    {
+        let pending = self.child_indexes(index).rev().collect();
+        self.retain_subtrees(pending, f);
+    }
+
+    /// Retains only the nodes of the subtrees at the pending indexes for which `f` returns
+    /// `true`. The pending indexes are visited from last to first.
+    fn retain_subtrees<F>(&mut self, mut pending: Vec<usize>, mut f: F)
+    where
+        F: FnMut(&mut N) -> bool,
+    {
+        while let Some(index) = pending.pop() {
+            let retain = match self.nodes.get_mut(index) {
+                Some(Some(value)) => f(value),
+                _ => continue,
+            };
+
+            if retain {
+                pending.extend(self.child_indexes(index).rev());
+            } else {
+                self.remove(index);
+            }
+        }
+    }
+
+    fn split_off_index(&mut self, index: usize) -> EytzingerTree<N> {
+        let mut new_tree =
+            EytzingerTree::with_index_calculator(self.index_calculator.subtree(index));
+
+        // get all of the indexes which should be moved out of the source tree
+        let indexes_to_move = self.node(index).map(|n| {
+            n.depth_first_iter(DepthFirstOrder::PreOrder)
+                .map(|n| n.index())
+                .collect::<Vec<_>>()
+        });
+
+        if let Some(indexes_to_move) = indexes_to_move {
+            let mut indexes_to_move_iter = indexes_to_move.into_iter();
+
+            if let Some(index_to_move) = indexes_to_move_iter.next() {
+                let new_root_value = self
+                    .take_value(index_to_move)
+                    .expect("there should be a value at the index returned by the iterator");
+
+                self.len -= 1;
+
+                let mut new_node = new_tree.set_root_value(new_root_value);
+
+                // this is used to determine if we need to move up a level
+                let mut previous_parent = self.parent_index(index_to_move);
+
+                for index_to_move in indexes_to_move_iter {
+                    let value_to_move = self
+                        .take_value(index_to_move)
+                        .expect("there should be a value at the index returned by the iterator");
+
+                    self.len -= 1;
+
+                    let current_parent = self
+                        .parent_index(index_to_move)
+                        .expect("the root should only ever be the first node in the iterator");
+
+                    if let Some(mut previous_parent) = previous_parent {
+                        while current_parent <= previous_parent {
+                            new_node = new_node.to_parent().ok().expect(
+                                "the root should only ever be the first node in the iterator",
+                            );
+                            previous_parent = self.parent_index(previous_parent).unwrap();
+                        }
+                    }
+
+                    previous_parent = Some(current_parent);
+
+                    let child_offset = index_to_move - self.child_index(current_parent, 0);
+                    new_node = new_node
+                        .to_child_entry(child_offset)
+                        .or_insert(value_to_move);
+                }
+            }
+        }
+
+        new_tree
+    }
+
+    /// Moves every node of `subtree` into this tree with its root at the specified vacant index.
+    /// `subtree` must have the arities of the levels from the index downwards.
+    fn graft(&mut self, index: usize, subtree: EytzingerTree<N>) {
+        // nodes are visited in breadth-first order so each parent is placed before its children
+        let mut destination_indexes = vec![None; subtree.nodes.len()];
+        for source_index in subtree.occupancy.iter() {
+            let destination_index = match subtree.parent_index(source_index) {
+                Some(source_parent) => {
+                    let child_offset = source_index - subtree.child_index(source_parent, 0);
+                    let destination_parent = destination_indexes[source_parent]
+                        .expect("a parent should be placed before its children");
+                    self.child_index(destination_parent, child_offset)
+                }
+                None => index,
+            };
+            destination_indexes[source_index] = Some(destination_index);
+        }
+
+        for (value, destination_index) in subtree.nodes.into_iter().zip(destination_indexes) {
+            if let (Some(value), Some(destination_index)) = (value, destination_index) {
+                self.set_value(destination_index, value);
+            }
+        }
+    }
+
+    fn set_value(&mut self, index: usize, new_value: N) -> NodeMut<'_, N> {
+        self.ensure_size(index);
+
+        let old_value = self.nodes[index].replace(new_value);
+        self.occupancy.insert(index);
+
+        if old_value.is_none() {
+            self.len += 1;
+        }
+
+        NodeMut { tree: self, index }
+    }
+
+    fn child_index(&self, parent_index: usize, child_offset: usize) -> usize {
+        self.index_calculator
+            .child_index(parent_index, child_offset)
+    }
+
+    fn parent_index(&self, child_index: usize) -> Option<usize> {
+        self.index_calculator.parent_index(child_index)
+    }
+
+    fn child_indexes(&self, parent_index: usize) -> Range<usize> {
+        self.index_calculator.child_indexes(parent_index)
+    }
+
+    /// Gets the number of children the node at the specified index may have.
+    fn child_count(&self, parent_index: usize) -> usize {
+        self.index_calculator.child_count(parent_index)
+    }
+
+    /// Gets the offset of the last occupied child of the node at the specified index.
+    fn last_child_offset(&self, index: usize) -> Option<usize> {
+        self.child_indexes(index)
+            .rposition(|child_index| self.node(child_index).is_some())
+    }
+
+    /// Gets the index for the specified path of child offsets from the root, `None` if any child
+    /// offset is out of range.
+    fn path_index(&self, path: &[usize]) -> Option<usize> {
+        self.index_calculator.path_index(path)
+    }
+
+    fn node(&self, index: usize) -> Option<Node<'_, N>> {
+        if self.occupancy.contains(index) {
+            Some(Node { tree: self, index })
+        } else {
+            None
+        }
+    }
+
+    fn node_mut(&mut self, index: usize) -> Result<NodeMut<'_, N>, &mut Self> {
+        if self.occupancy.contains(index) {
+            Ok(NodeMut { tree: self, index })
+        } else {
+            Err(self)
+        }
+    }
+
+    fn entry_at(&mut self, index: usize) -> Entry<'_, N> {
+        match self.node_mut(index) {
+            Ok(node) => Entry::Occupied(node),
+            Err(tree) => Entry::Vacant(VacantEntry { tree, index }),
+        }
+    }
+
+    fn child_entry(&mut self, parent: usize, child: usize) -> Entry<'_, N> {
+        let child_index = self.child_index(parent, child);
+        self.entry_at(child_index)
+    }
+
+    /// Takes the value at the specified index leaving the slot vacant. This does not update `len`.
+    fn take_value(&mut self, index: usize) -> Option<N> {
+        self.occupancy.remove(index);
+        self.nodes.get_mut(index).and_then(Option::take)
+    }
+
+    /// Gets whether the occupancy and `len` are consistent with the occupied slots.
+    #[cfg(test)]
+    fn is_consistent(&self) -> bool {
+        let occupied_count = self.nodes.iter().flatten().count();
+
+        self.len == occupied_count
+            && self.occupancy.iter().count() == occupied_count
+            && self
+                .nodes
+                .iter()
+                .enumerate()
+                .all(|(index, slot)| slot.is_some() == self.occupancy.contains(index))
+    }
+
+    fn parent(&self, child: usize) -> Option<Node<'_, N>> {
+        let parent_index = self.parent_index(child)?;
+        self.node(parent_index)
+    }
+
+    fn parent_mut(&mut self, child: usize) -> Result<NodeMut<'_, N>, &mut Self> {
+        if let Some(parent_index) = self.parent_index(child) {
+            self.node_mut(parent_index)
+        } else {
+            Err(self)
+        }
+    }
+
+    fn child(&self, parent: usize, child: usize) -> Option<Node<'_, N>> {
+        let child_index = self.child_index(parent, child);
+        self.node(child_index)
+    }
+
+    fn child_mut(&mut self, parent: usize, child: usize) -> Result<NodeMut<'_, N>, &mut Self> {
+        let child_index = self.child_index(parent, child);
+        self.node_mut(child_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entry::EntryRef,
+        error::{
+            ArityChangeError, CopyError, CorruptionError, PathError, ReplaceError, RootOccupied,
+            SpliceError, SwapError,
+        },
+        test_util::{bst_tree, sample_tree},
+        DepthFirstOrder, EytzingerTree, Node, OverwritePolicy, TreeEvent, TreeVisitor,
+    };
+    use matches::assert_matches;
+    use std::{
+        cmp::Ordering,
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn root_is_none_for_empty() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+
+        assert_matches!(tree.root(), None);
+        assert_matches!(tree.root_mut(), None);
+    }
+
+    #[test]
+    fn set_root_value_sets_root() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+
+        let expected_root = 5;
+        tree.set_root_value(expected_root);
+
+        assert_eq!(tree.root().map(|x| *x.value()).unwrap(), expected_root);
+        assert_eq!(tree.root_mut().map(|x| *x.value()).unwrap(), expected_root);
+    }
+
+    #[test]
+    fn depth_first_iter_returns_empty_for_empty_tree() {
+        let tree = EytzingerTree::<u32>::new(2);
+
+        assert_matches!(
+            tree.depth_first_iter(DepthFirstOrder::PostOrder).next(),
+            None
+        )
+    }
+
+    #[test]
+    fn depth_first_iter_returns_depth_first() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            {
+                let mut left = root.set_child_value(0, 2);
+
+                left.set_child_value(0, 1);
+                let mut left_right = left.set_child_value(1, 4);
+                left_right.set_child_value(0, 3);
+            }
+            {
+                let mut right = root.set_child_value(1, 7);
+                right.set_child_value(1, 8);
+            }
+        }
+
+        assert_eq!(tree.len(), 7);
+
+        let depth_first: Vec<_> = tree
+            .depth_first_iter(DepthFirstOrder::PreOrder)
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(depth_first, vec![5, 2, 1, 4, 3, 7, 8]);
+
+        let depth_first: Vec<_> = tree
+            .depth_first_iter(DepthFirstOrder::PostOrder)
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(depth_first, vec![1, 3, 4, 2, 8, 7, 5]);
+    }
+
+    #[test]
+    fn into_depth_first_iterator_pre_order() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            {
+                let mut left = root.set_child_value(0, 2);
+
+                left.set_child_value(0, 1);
+                let mut left_right = left.set_child_value(1, 4);
+                left_right.set_child_value(0, 3);
+            }
+            {
+                let mut right = root.set_child_value(1, 7);
+                right.set_child_value(1, 8);
+            }
+        }
+
+        assert_eq!(tree.len(), 7);
+
+        let depth_first: Vec<_> = tree
+            .into_depth_first_iterator(DepthFirstOrder::PreOrder)
+            .collect();
+
+        assert_eq!(depth_first, vec![5, 2, 1, 4, 3, 7, 8]);
+    }
+
+    #[test]
+    fn into_depth_first_iterator_post_order() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            {
+                let mut left = root.set_child_value(0, 2);
+
+                left.set_child_value(0, 1);
+                let mut left_right = left.set_child_value(1, 4);
+                left_right.set_child_value(0, 3);
+            }
+            {
+                let mut right = root.set_child_value(1, 7);
+                right.set_child_value(1, 8);
+            }
+        }
+
+        assert_eq!(tree.len(), 7);
+
+        let depth_first: Vec<_> = tree
+            .into_depth_first_iterator(DepthFirstOrder::PostOrder)
+            .collect();
+
+        assert_eq!(depth_first, vec![1, 3, 4, 2, 8, 7, 5]);
+    }
+
+    #[test]
+    fn breadth_first_iter_returns_empty_for_empty_tree() {
+        let tree = EytzingerTree::<u32>::new(2);
+
+        assert_matches!(tree.breadth_first_iter().next(), None)
+    }
+
+    #[test]
+    fn breadth_first_iter_returns_breadth_first() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            {
+                let mut left = root.set_child_value(0, 2);
+
+                left.set_child_value(0, 1);
+                let mut left_right = left.set_child_value(1, 4);
+                left_right.set_child_value(0, 3);
+            }
+            {
+                let mut right = root.set_child_value(1, 7);
+                right.set_child_value(1, 8);
+            }
+        }
+
+        assert_eq!(tree.len(), 7);
+
+        let breadth_first: Vec<_> = tree
+            .breadth_first_iter()
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(breadth_first, vec![5, 2, 7, 1, 4, 8, 3]);
+    }
+
+    #[test]
+    fn into_iter_for_reference() {
+        let tree = sample_tree();
+
+        assert_eq!((&tree).into_iter().count(), tree.len());
+
+        let mut values = vec![];
+        for node in &tree {
+            values.push(*node.value());
+        }
+        assert_eq!(values, vec![5, 2, 7, 1, 4, 8, 3]);
+    }
+
+    #[test]
+    fn breadth_first_iter_dense_for_complete_tree() {
+        let tree = EytzingerTree::perfect_from_fn(3, 3, |path| path.to_vec());
+
+        assert!(tree
+            .breadth_first_iter_dense()
+            .eq(tree.breadth_first_iter()));
+    }
+
+    #[test]
+    fn breadth_first_iter_dense_for_sample_tree() {
+        let tree = sample_tree();
+
+        let breadth_first: Vec<_> = tree
+            .breadth_first_iter_dense()
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(breadth_first, vec![5, 2, 7, 1, 4, 8, 3]);
+        assert!(tree
+            .breadth_first_iter_dense()
+            .eq(tree.breadth_first_iter()));
+    }
+
+    #[test]
+    fn checked_breadth_first_iter() {
+        let mut tree = sample_tree();
+
+        let values: Result<Vec<_>, _> = tree
+            .checked_breadth_first_iter()
+            .map(|n| n.map(|n| *n.value()))
+            .collect();
+        assert_eq!(values, Ok(vec![5, 2, 7, 1, 4, 8, 3]));
+
+        // clear a slot directly, leaving its children in place
+        tree.nodes[4] = None;
+        tree.occupancy.remove(4);
+        tree.len -= 1;
+
+        let results: Vec<_> = tree
+            .checked_breadth_first_iter()
+            .map(|n| n.map(|n| *n.value()))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                Ok(5),
+                Ok(2),
+                Ok(7),
+                Ok(1),
+                Ok(8),
+                Err(CorruptionError::OrphanedNode {
+                    path: vec![0, 1, 0]
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn breadth_first_iter_rev_returns_reverse_breadth_first() {
+        let tree = sample_tree();
+
+        let breadth_first_rev: Vec<_> = tree
+            .breadth_first_iter_rev()
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(breadth_first_rev, vec![3, 8, 4, 1, 7, 2, 5]);
+    }
+
+    #[test]
+    fn iter_with_parent() {
+        let tree = sample_tree();
+
+        let pairs: Vec<_> = tree
+            .iter_with_parent()
+            .map(|(parent, node)| (parent.map(|p| *p.value()), *node.value()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (None, 5),
+                (Some(5), 2),
+                (Some(5), 7),
+                (Some(2), 1),
+                (Some(2), 4),
+                (Some(7), 8),
+                (Some(4), 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn root_to_leaf_paths() {
+        let tree = sample_tree();
+
+        let paths: Vec<Vec<_>> = tree
+            .root_to_leaf_paths()
+            .map(|path| path.iter().map(|n| *n.value()).collect())
+            .collect();
+        assert_eq!(paths, vec![vec![5, 2, 1], vec![5, 2, 4, 3], vec![5, 7, 8]]);
+
+        assert_eq!(EytzingerTree::<u32>::new(2).root_to_leaf_paths().count(), 0);
+    }
+
+    #[test]
+    fn fold_paths() {
+        let tree = sample_tree();
+
+        let sums = tree.fold_paths(|values| values.iter().copied().sum::<u32>());
+        assert_eq!(sums, vec![8, 14, 20]);
+
+        let lengths = tree.fold_paths(|values| values.len());
+        assert_eq!(lengths, vec![3, 4, 3]);
+    }
+
+    #[test]
+    fn nodes_at_depth() {
+        let tree = sample_tree();
+        let values_at_depth = |depth| {
+            tree.nodes_at_depth(depth)
+                .map(|n| *n.value())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(values_at_depth(0), vec![5]);
+        assert_eq!(values_at_depth(2), vec![1, 4, 8]);
+        assert_eq!(values_at_depth(3), vec![3]);
+        assert_eq!(values_at_depth(4), Vec::<u32>::new());
+        assert_eq!(values_at_depth(100), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn bfs_rank() {
+        let tree = sample_tree();
+
+        for (rank, node) in tree.breadth_first_iter().enumerate() {
+            assert_eq!(tree.bfs_rank(&node), rank);
+            assert!(tree.node_at_bfs_rank(rank).unwrap().ptr_eq(&node));
+        }
+
+        assert_eq!(tree.bfs_rank(&tree.root().unwrap()), 0);
+        assert_eq!(tree.bfs_rank(&tree.node_at_path(&[1, 1]).unwrap()), 5);
+        assert_eq!(tree.node_at_bfs_rank(5).map(|n| *n.value()), Some(8));
+        assert!(tree.node_at_bfs_rank(7).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "the node should be from this tree")]
+    fn bfs_rank_other_tree_panics() {
+        let tree = sample_tree();
+        let other = sample_tree();
+
+        tree.bfs_rank(&other.root().unwrap());
+    }
+
+    #[test]
+    fn in_order_iter() {
+        let tree = sample_tree();
+
+        let values: Vec<_> = tree.in_order_iter().map(|n| *n.value()).collect();
+        assert_eq!(values, vec![1, 2, 3, 4, 5, 7, 8]);
+
+        assert_eq!(EytzingerTree::<u32>::new(2).in_order_iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "operation requires max_children_per_node == 2, got 3")]
+    fn in_order_iter_requires_binary_tree() {
+        EytzingerTree::<u32>::new(3).in_order_iter().count();
+    }
+
+    #[test]
+    fn expand_from() {
+        let tree = EytzingerTree::expand_from(1, 2, 2, |&parent, index| Some(parent * 2 + index));
+
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6, 7]
+        );
+        assert!(tree.is_perfect());
+        assert_eq!(tree.height(), Some(2));
+    }
+
+    #[test]
+    fn expand_from_skips_vacant_children() {
+        let tree = EytzingerTree::expand_from(1, 3, 10, |&parent, index| {
+            if parent < 20 && index != 1 {
+                Some(parent * 3 + index)
+            } else {
+                None
+            }
+        });
+
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![1, 3, 5, 9, 11, 15, 17, 27, 29, 33, 35, 45, 47, 51, 53]
+        );
+        assert!(tree.is_consistent());
+
+        let tree = EytzingerTree::expand_from(1, 2, 0, |_, _| Some(0));
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn assert_shape() {
+        sample_tree().assert_shape(&[
+            (vec![1, 1], 8),
+            (vec![], 5),
+            (vec![0], 2),
+            (vec![1], 7),
+            (vec![0, 0], 1),
+            (vec![0, 1], 4),
+            (vec![0, 1, 0], 3),
+        ]);
+        EytzingerTree::<u32>::new(2).assert_shape(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the tree does not have the expected shape:\n  \
+                               [0]: expected 3, found 2\n  \
+                               [1, 0]: expected 6, found no node\n  \
+                               [0, 0]: unexpected 1")]
+    fn assert_shape_differences() {
+        sample_tree().assert_shape(&[
+            (vec![], 5),
+            (vec![0], 3),
+            (vec![1], 7),
+            (vec![1, 0], 6),
+            (vec![0, 1], 4),
+            (vec![0, 1, 0], 3),
+            (vec![1, 1], 8),
+        ]);
+    }
+
+    #[test]
+    fn frontier() {
+        let tree = sample_tree();
+
+        let values: Vec<_> = tree.frontier().iter().map(|n| *n.value()).collect();
+        assert_eq!(values, vec![7, 1, 4, 8, 3]);
+
+        let tree = EytzingerTree::perfect_from_fn(2, 1, |_| ());
+        assert_eq!(tree.frontier().len(), 2);
+        assert!(EytzingerTree::<u32>::new(2).frontier().is_empty());
+    }
+
+    #[test]
+    fn breadth_first_iter_to_depth() {
+        let tree = sample_tree();
+
+        let values: Vec<_> = tree
+            .breadth_first_iter_to_depth(1)
+            .map(|n| *n.value())
+            .collect();
+
+        assert_eq!(values, vec![5, 2, 7]);
+    }
+
+    #[test]
+    fn events_iter() {
+        let tree = sample_tree();
+
+        let events: Vec<_> = tree
+            .events_iter()
+            .map(|event| match event {
+                TreeEvent::Enter(n) => (true, *n.value()),
+                TreeEvent::Leave(n) => (false, *n.value()),
+            })
+            .collect();
+
+        assert_eq!(events.len(), 14);
+        assert_eq!(
+            events,
+            vec![
+                (true, 5),
+                (true, 2),
+                (true, 1),
+                (false, 1),
+                (true, 4),
+                (true, 3),
+                (false, 3),
+                (false, 4),
+                (false, 2),
+                (true, 7),
+                (true, 8),
+                (false, 8),
+                (false, 7),
+                (false, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn visit() {
+        // writes each node with its children in brackets, vacant child slots before an occupied
+        // one are left empty
+        #[derive(Default)]
+        struct BracketVisitor {
+            output: String,
+            last_child_offsets: Vec<Option<usize>>,
+        }
+
+        impl TreeVisitor<u32> for BracketVisitor {
+            fn enter(&mut self, node: &Node<'_, u32>) {
+                if let Some(parent) = node.parent() {
+                    let (offset, _) = parent
+                        .children_indexed()
+                        .find(|(_, child)| child.ptr_eq(node))
+                        .unwrap();
+                    let last_child_offset = self.last_child_offsets.last_mut().unwrap();
+
+                    let separators = match *last_child_offset {
+                        Some(last_offset) => offset - last_offset,
+                        None => {
+                            self.output.push('(');
+                            offset
+                        }
+                    };
+                    for _ in 0..separators {
+                        self.output.push(',');
+                    }
+                    *last_child_offset = Some(offset);
+                }
+
+                self.output.push_str(&node.value().to_string());
+                self.last_child_offsets.push(None);
+            }
+
+            fn leave(&mut self, _: &Node<'_, u32>) {
+                if let Some(Some(_)) = self.last_child_offsets.pop() {
+                    self.output.push(')');
+                }
+            }
+        }
+
+        let mut visitor = BracketVisitor::default();
+        sample_tree().visit(&mut visitor);
+
+        assert_eq!(visitor.output, "5(2(1,4(3)),7(,8))");
+        assert!(visitor.last_child_offsets.is_empty());
+    }
+
+    #[test]
+    fn interior_iter() {
+        let tree = sample_tree();
+
+        let values: Vec<_> = tree.interior_iter().map(|n| *n.value()).collect();
+
+        assert_eq!(values, vec![5, 2, 7, 4]);
+    }
+
+    #[test]
+    fn iterative_deepening_iter() {
+        let tree = sample_tree();
+
+        let visits: Vec<_> = tree
+            .iterative_deepening_iter(2)
+            .map(|(depth, n)| (depth, *n.value()))
+            .collect();
+
+        assert_eq!(
+            visits,
+            vec![
+                (0, 5),
+                (0, 5),
+                (1, 2),
+                (1, 7),
+                (0, 5),
+                (1, 2),
+                (2, 1),
+                (2, 4),
+                (1, 7),
+                (2, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn iterative_deepening_iter_empty() {
+        let tree = EytzingerTree::<u32>::new(2);
+
+        assert_matches!(tree.iterative_deepening_iter(2).next(), None);
+    }
+
+    #[test]
+    fn find_all() {
+        let tree = sample_tree();
+
+        let values: Vec<_> = tree
+            .find_all(|&v| v % 2 == 0)
+            .iter()
+            .map(|n| *n.value())
+            .collect();
+        assert_eq!(values, vec![2, 4, 8]);
+
+        assert!(tree.find_all(|&v| v > 8).is_empty());
+    }
+
+    #[test]
+    fn find_path() {
+        let tree = sample_tree();
+
+        assert_eq!(tree.find_path(|&v| v == 4), Some(vec![0, 1]));
+        assert_eq!(tree.find_path(|&v| v == 5), Some(vec![]));
+        assert_eq!(tree.find_path(|&v| v > 2), Some(vec![]));
+        assert_eq!(tree.find_path(|&v| v < 5), Some(vec![0]));
+        assert_eq!(tree.find_path(|&v| v == 6), None);
+    }
+
+    #[test]
+    fn contains_subtree() {
+        let tree = sample_tree();
+
+        let mut other = EytzingerTree::new(2);
+        other.set_root_value(4);
+        assert_eq!(tree.contains_subtree(&other), Some(vec![0, 1]));
+
+        other.root_mut().unwrap().set_child_value(0, 3);
+        assert_eq!(tree.contains_subtree(&other), Some(vec![0, 1]));
+
+        other.root_mut().unwrap().set_child_value(1, 3);
+        assert_eq!(tree.contains_subtree(&other), None);
+
+        let mut other = EytzingerTree::new(3);
+        other.set_root_value(4);
+        assert_eq!(tree.contains_subtree(&other), None);
+
+        assert_eq!(tree.contains_subtree(&EytzingerTree::new(2)), Some(vec![]));
+        assert_eq!(tree.contains_subtree(&tree), Some(vec![]));
+    }
+
+    #[test]
+    fn get_disjoint_mut() {
+        let mut tree = sample_tree();
+
+        {
+            let mut values = tree.get_disjoint_mut(&[&[1], &[], &[0]]).unwrap();
+            assert_eq!(
+                values.iter().map(|v| **v).collect::<Vec<_>>(),
+                vec![7, 5, 2]
+            );
+            *values[0] += 1;
+            *values[1] += 10;
+            *values[2] *= 3;
+        }
+
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![15, 6, 8, 1, 4, 8, 3]
+        );
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn get_disjoint_mut_rejects_overlapping_or_vacant_paths() {
+        let mut tree = sample_tree();
+
+        assert!(tree.get_disjoint_mut(&[&[0], &[1], &[0]]).is_none());
+        assert!(tree.get_disjoint_mut(&[&[], &[1, 0]]).is_none());
+        assert!(tree.get_disjoint_mut(&[&[2]]).is_none());
+        assert_eq!(
+            tree.get_disjoint_mut(&[]).map(|values| values.len()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn node_at_path() {
+        let tree = sample_tree();
+
+        assert_eq!(tree.node_at_path(&[]).map(|n| *n.value()), Some(5));
+        assert_eq!(tree.node_at_path(&[0, 1, 0]).map(|n| *n.value()), Some(3));
+        assert_matches!(tree.node_at_path(&[1, 0]), None);
+        assert_matches!(tree.node_at_path(&[2]), None);
+    }
+
+    #[test]
+    fn swap() {
+        let mut tree = sample_tree();
+        let mut other = EytzingerTree::new(3);
+
+        tree.swap(&mut other);
+
+        assert!(tree.is_empty());
+        assert_eq!(tree.max_children_per_node(), 3);
+        assert_eq!(other.len(), 7);
+        assert_eq!(other, sample_tree());
+    }
+
+    #[test]
+    fn clear_drops_each_value_once() {
+        use std::{cell::Cell, rc::Rc};
+
+        struct DropCounter(Rc<Cell<usize>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        let mut tree = sample_tree().map(|_| DropCounter(drops.clone()));
+        let len = tree.len();
+        assert!(tree.nodes.len() > len);
+
+        tree.clear();
+
+        assert_eq!(drops.get(), len);
+        assert_eq!(tree.len(), 0);
+        assert!(tree.root().is_none());
+        assert!(tree.is_consistent());
+
+        drop(tree);
+        assert_eq!(drops.get(), len);
+    }
+
+    #[test]
+    fn trim_leaves() {
+        let mut tree = sample_tree();
+
+        assert_eq!(tree.trim_leaves(), 3);
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![5, 2, 7, 4]
+        );
+        assert_eq!(tree.node_at_path(&[0, 1]).map(|n| *n.value()), Some(4));
+        assert!(tree.is_consistent());
+
+        assert_eq!(tree.trim_leaves(), 2);
+        assert_eq!(tree.trim_leaves(), 1);
+        assert_eq!(tree.trim_leaves(), 1);
+        assert_eq!(tree.trim_leaves(), 0);
+        assert!(tree.is_empty());
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn drop_post_order() {
+        use std::{cell::RefCell, rc::Rc};
+
+        struct DropRecorder(u32, Rc<RefCell<Vec<u32>>>);
+
+        impl Drop for DropRecorder {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+
+        let dropped = Rc::new(RefCell::new(Vec::new()));
+        let tree = sample_tree().map(|value| DropRecorder(value, dropped.clone()));
+
+        tree.drop_post_order();
+
+        assert_eq!(*dropped.borrow(), vec![1, 3, 4, 2, 8, 7, 5]);
+    }
+
+    #[test]
+    fn reserve_depth() {
+        let mut tree = EytzingerTree::new(2);
+        tree.reserve_depth(3);
+
+        let capacity = tree.capacity();
+        assert!(capacity >= 15);
+
+        let mut pending = vec![tree.set_root_value(0).index];
+        while let Some(index) = pending.pop() {
+            if tree.index_calculator.depth(index) < 3 {
+                for child_index in tree.child_indexes(index) {
+                    tree.set_value(child_index, child_index);
+                    pending.push(child_index);
+                }
+            }
+        }
+
+        assert_eq!(tree.len(), 15);
+        assert!(tree.is_perfect());
+        assert_eq!(tree.capacity(), capacity);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_depth_overflow_panics() {
+        EytzingerTree::<u32>::new(2).reserve_depth(64);
+    }
+    #[test]
+    fn cycle_children() {
+        let mut tree = sample_tree();
+
+        tree.cycle_children();
+
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.node_at_path(&[0]).map(|n| *n.value()), Some(7));
+        assert_eq!(tree.node_at_path(&[0, 0]).map(|n| *n.value()), Some(8));
+        assert_eq!(tree.node_at_path(&[1]).map(|n| *n.value()), Some(2));
+        assert_eq!(tree.node_at_path(&[1, 0]).map(|n| *n.value()), Some(4));
+        assert_eq!(tree.node_at_path(&[1, 0, 1]).map(|n| *n.value()), Some(3));
+        assert_eq!(tree.node_at_path(&[1, 1]).map(|n| *n.value()), Some(1));
+        assert!(tree.is_consistent());
+
+        tree.cycle_children();
+        assert_eq!(tree, sample_tree());
+    }
+
+    #[test]
+    fn cycle_children_restores_after_arity_cycles() {
+        let original = EytzingerTree::perfect_from_fn(3, 2, |path| path.to_vec());
+        let mut tree = original.clone();
+
+        tree.cycle_children();
+        assert_ne!(tree, original);
+        assert_eq!(tree.node_at_path(&[1, 2]).unwrap().value(), &vec![0, 1]);
+
+        tree.cycle_children();
+        tree.cycle_children();
+        assert_eq!(tree, original);
+    }
+
+    #[test]
+    fn compact_clone() {
+        let mut tree = sample_tree();
+        tree.root_mut()
+            .unwrap()
+            .child_mut(0)
+            .unwrap()
+            .set_child_value(1, 6)
+            .set_child_value(1, 9);
+        tree.split_off(&[0, 1]);
+
+        let compact = tree.compact_clone();
+
+        assert_eq!(compact, tree);
+        assert_eq!(compact.nodes.len(), 7);
+        assert_eq!(compact.nodes.capacity(), 7);
+        assert!(compact.nodes.capacity() < tree.clone().nodes.capacity());
+        assert!(compact.is_consistent());
+    }
+
+    #[test]
+    fn truncate_unused() {
+        let mut tree = sample_tree();
+        let slots = tree.nodes.len();
+        let capacity = tree.nodes.capacity();
+
+        tree.split_off(&[0, 1]);
+
+        assert_eq!(tree.truncate_unused(), slots - 7);
+        assert_eq!(tree.nodes.len(), 7);
+        assert_eq!(tree.nodes.capacity(), capacity);
+        assert_eq!(tree.truncate_unused(), 0);
+        assert!(tree.is_consistent());
+
+        let breadth_first: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(breadth_first, vec![5, 2, 7, 1, 8]);
+    }
+
+    #[test]
+    fn shrink_to_fit_keeps_sparse_nodes() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        tree.set_root_value(5)
+            .set_child_value(1, 7)
+            .set_child_value(1, 8);
+        tree.reserve_depth(4);
+
+        tree.shrink_to_fit();
+
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.nodes.len(), 7);
+        assert_eq!(tree.nodes.capacity(), 7);
+        assert_eq!(tree.node_at_path(&[1, 1]).map(|n| *n.value()), Some(8));
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn mixed_arity() {
+        let mut tree = EytzingerTree::mixed_arity(vec![3, 2]);
+        {
+            let mut root = tree.set_root_value(0);
+            root.set_child_value(0, 1);
+            {
+                let mut middle = root.set_child_value(1, 2);
+                middle.set_child_value(1, 5);
+                middle.set_child_value(0, 4).set_child_value(1, 6);
+            }
+            root.set_child_value(2, 3);
+        }
+
+        assert_eq!(tree.max_children_per_node(), 3);
+        assert_eq!(tree.len(), 7);
+        assert!(tree.is_consistent());
+
+        let breadth_first: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(breadth_first, vec![0, 1, 2, 3, 4, 5, 6]);
+
+        let pre_order: Vec<_> = tree
+            .depth_first_iter(DepthFirstOrder::PreOrder)
+            .map(|n| *n.value())
+            .collect();
+        assert_eq!(pre_order, vec![0, 1, 2, 4, 6, 5, 3]);
+
+        let leaf = tree.node_at_path(&[1, 0, 1]).unwrap();
+        assert_eq!(leaf.value(), &6);
+        assert_eq!(leaf.parent().unwrap().parent().unwrap().value(), &2);
+        assert!(tree.node_at_path(&[1, 2]).is_none());
+        assert_eq!(tree.count_at_depth(2), 2);
+
+        let owned: Vec<_> = tree
+            .clone()
+            .into_depth_first_iterator(DepthFirstOrder::PostOrder)
+            .collect();
+        assert_eq!(owned, vec![1, 6, 4, 5, 2, 3, 0]);
+
+        let split_off = tree.split_off(&[1]).unwrap();
+        assert_eq!(split_off.max_children_per_node(), 2);
+        let breadth_first: Vec<_> = split_off.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(breadth_first, vec![2, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the child index 2 should be less than the arity of its parent's level (2)"
+    )]
+    fn mixed_arity_child_out_of_range_panics() {
+        let mut tree = EytzingerTree::mixed_arity(vec![3, 2]);
+
+        tree.set_root_value(0)
+            .set_child_value(2, 1)
+            .set_child_value(2, 2);
+    }
+
+    #[test]
+    fn replace_subtree() {
+        let mut tree = sample_tree();
+        let mut replacement = EytzingerTree::new(2);
+        replacement.set_root_value(9);
+
+        let old_subtree = tree.replace_subtree(&[0], replacement).unwrap();
+
+        let old_values: Vec<_> = old_subtree
+            .breadth_first_iter()
+            .map(|n| *n.value())
+            .collect();
+        assert_eq!(old_values, vec![2, 1, 4, 3]);
+
+        let values: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(values, vec![5, 9, 7, 8]);
+        assert_eq!(tree.len(), 4);
+        assert!(tree.is_consistent());
+
+        tree.replace_subtree(&[0], old_subtree).unwrap();
+        assert_eq!(tree, sample_tree());
+    }
+
+    #[test]
+    fn replace_subtree_errors() {
+        let mut tree = sample_tree();
+
+        assert_eq!(
+            tree.replace_subtree(&[0, 0, 0], EytzingerTree::new(2)),
+            Err(ReplaceError::Vacant {
+                path: vec![0, 0, 0]
+            })
+        );
+        assert_eq!(
+            tree.replace_subtree(&[0], EytzingerTree::new(3)),
+            Err(ReplaceError::ArityMismatch)
+        );
+        assert_eq!(tree, sample_tree());
+    }
+
+    #[test]
+    fn splice_out() {
+        let mut tree = sample_tree();
+
+        assert_eq!(tree.splice_out(&[1]), Ok(7));
+
+        assert_eq!(tree.node_at_path(&[1]).map(|n| *n.value()), Some(8));
+        assert!(tree.node_at_path(&[1, 1]).is_none());
+        let values: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(values, vec![5, 2, 8, 1, 4, 3]);
+        assert_eq!(tree.len(), 6);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn splice_out_moves_subtrees_into_vacant_slots() {
+        let mut tree = sample_tree();
+        tree.split_off(&[1]);
+
+        assert_eq!(tree.splice_out(&[0]), Ok(2));
+
+        assert_eq!(tree.node_at_path(&[0]).map(|n| *n.value()), Some(1));
+        assert_eq!(tree.node_at_path(&[1]).map(|n| *n.value()), Some(4));
+        assert_eq!(tree.node_at_path(&[1, 0]).map(|n| *n.value()), Some(3));
+        assert_eq!(tree.len(), 4);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn splice_out_errors() {
+        let mut tree = sample_tree();
+
+        assert_eq!(tree.splice_out(&[]), Err(SpliceError::IsRoot));
+        assert_eq!(
+            tree.splice_out(&[1, 0]),
+            Err(SpliceError::Vacant { path: vec![1, 0] })
+        );
+        assert_eq!(tree.splice_out(&[0]), Err(SpliceError::InsufficientSlots));
+        assert_eq!(tree, sample_tree());
+    }
+
+    #[test]
+    fn arity_one_chain() {
+        let mut tree = EytzingerTree::new(1);
+        {
+            let mut node = tree.set_root_value(0);
+            for value in 1..5 {
+                node = node.to_child_entry(0).or_insert(value);
+            }
+        }
+
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.height(), Some(4));
+        assert_eq!(tree.nodes.len(), 5);
+        assert_eq!(tree.child_flat_index(3, 0), 4);
+        assert_eq!(tree.parent_flat_index(4), Some(3));
+        assert_eq!(tree.node_at_path(&[0, 0, 0]).map(|n| *n.value()), Some(3));
+
+        let pre_order: Vec<_> = tree
+            .depth_first_iter(DepthFirstOrder::PreOrder)
+            .map(|n| *n.value())
+            .collect();
+        assert_eq!(pre_order, vec![0, 1, 2, 3, 4]);
+
+        let post_order: Vec<_> = tree
+            .depth_first_iter(DepthFirstOrder::PostOrder)
+            .map(|n| *n.value())
+            .collect();
+        assert_eq!(post_order, vec![4, 3, 2, 1, 0]);
+
+        let breadth_first: Vec<_> = tree.into_breadth_first_iterator().collect();
+        assert_eq!(breadth_first, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_children_per_node should be greater than 0")]
+    fn arity_zero_panics() {
+        EytzingerTree::<u32>::new(0);
+    }
+
+    #[test]
+    fn from_complete_vec() {
+        let tree = EytzingerTree::from_complete_vec(2, vec![5, 2, 7, 1, 4, 8]);
+
+        assert_eq!(tree.len(), 6);
+        assert!(tree.is_complete());
+        assert!(tree.is_consistent());
+
+        let breadth_first: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(breadth_first, vec![5, 2, 7, 1, 4, 8]);
+        assert_eq!(tree.node_at_path(&[1, 0]).map(|n| *n.value()), Some(8));
+    }
+
+    #[test]
+    fn set_arity() {
+        let mut tree = EytzingerTree::new(2);
+        assert_eq!(tree.set_arity(3), Ok(()));
+        assert_eq!(tree.max_children_per_node(), 3);
+
+        tree.set_root_value(5).set_child_value(2, 6);
+        assert_eq!(tree.node_at_path(&[2]).map(|n| *n.value()), Some(6));
+
+        let mut tree = sample_tree();
+        assert_eq!(tree.set_arity(3), Err(ArityChangeError::HasChildren));
+        assert_eq!(tree, sample_tree());
+
+        let mut tree = sample_tree();
+        tree.root_mut().unwrap().set_children(vec![None, None]);
+        assert_eq!(tree.set_arity(4), Ok(()));
+        assert_eq!(tree.root().map(|n| *n.value()), Some(5));
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn entry_mut() {
+        let mut tree = sample_tree();
+
+        assert_eq!(*tree.entry_mut(&[0, 0]).unwrap().or_insert(9).value(), 1);
+        assert_eq!(*tree.entry_mut(&[1, 0]).unwrap().or_insert(9).value(), 9);
+        assert_eq!(tree.node_at_path(&[1, 0]).map(|n| *n.value()), Some(9));
+        assert_eq!(tree.len(), 8);
+
+        assert!(tree.entry_mut(&[1, 2]).is_none());
+        assert!(tree.entry_mut(&[0, 0, 0, 0]).is_none());
+        assert!(tree.is_consistent());
+
+        let mut empty = EytzingerTree::new(2);
+        empty.entry_mut(&[]).unwrap().or_insert(5);
+        assert_eq!(empty.root().map(|n| *n.value()), Some(5));
+    }
+
+    #[test]
+    fn entry() {
+        let tree = sample_tree();
+
+        assert_matches!(tree.entry(&[0, 0]), Some(EntryRef::Occupied(node)) if *node.value() == 1);
+        assert_matches!(
+            tree.entry(&[1, 0]),
+            Some(EntryRef::Vacant(vacant)) if vacant.parent().map(|n| *n.value()) == Some(7)
+        );
+        assert!(tree.entry(&[1, 2]).is_none());
+        assert!(tree.entry(&[0, 0, 0, 0]).is_none());
+        assert_matches!(
+            EytzingerTree::<u32>::new(2).entry(&[]),
+            Some(EntryRef::Vacant(_))
+        );
+    }
+
+    #[test]
+    fn set_root_value_after_clear() {
+        let mut tree = sample_tree();
+
+        tree.clear();
+        assert_eq!(tree, EytzingerTree::new(2));
+
+        tree.set_root_value(3);
+        assert_eq!(tree.len(), 1);
+        assert!(tree.is_consistent());
+
+        let mut removed = sample_tree();
+        removed.remove_root_value();
+        removed.set_root_value(3);
+        assert_eq!(tree, removed);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_path_map() {
+        let tree = sample_tree();
+        let map = tree.to_path_map();
+
+        assert_eq!(map.len(), 7);
+        assert_eq!(map[&vec![]], &5);
+        assert_eq!(map[&vec![0, 1, 0]], &3);
+        assert_eq!(map.get(&vec![1, 0]), None);
+    }
+
+    #[test]
+    fn try_set_root_value_refuses_to_replace() {
+        let mut tree = EytzingerTree::new(2);
+
+        assert_eq!(tree.try_set_root_value(5).map(|n| *n.value()), Ok(5));
+        assert_eq!(
+            tree.try_set_root_value(6).map(|n| *n.value()),
+            Err(RootOccupied { value: 6 })
+        );
+        assert_eq!(tree.root().map(|n| *n.value()), Some(5));
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn for_each_mut_visits_breadth_first() {
+        let mut tree = sample_tree();
+
+        let mut visited = vec![];
+        tree.for_each_mut(|value| {
+            visited.push(*value);
+            *value += 10;
+        });
+
+        assert_eq!(visited, vec![5, 2, 7, 1, 4, 8, 3]);
+        let values: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(values, vec![15, 12, 17, 11, 14, 18, 13]);
+    }
+
+    #[test]
+    fn retain_mut() {
+        let mut tree = sample_tree().map(|value| value + 1);
+
+        tree.retain_mut(|value| {
+            if *value % 2 == 1 {
+                return false;
+            }
+            *value *= 2;
+            true
+        });
+
+        assert_eq!(tree.into_path_pairs(), vec![(vec![], 12), (vec![1], 16)]);
+
+        let mut tree = sample_tree();
+        tree.retain_mut(|value| *value != 5);
+        assert!(tree.is_empty());
+        assert!(tree.is_consistent());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn find_duplicate_subtrees() {
+        let mut tree = EytzingerTree::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            root.set_child_value(0, 2).set_child_value(0, 1);
+            root.set_child_value(1, 2).set_child_value(0, 1);
+        }
+
+        assert_eq!(
+            tree.find_duplicate_subtrees(),
+            vec![vec![vec![0], vec![1]], vec![vec![0, 0], vec![1, 0]]]
+        );
+
+        // the same values in a different shape are not duplicates
+        tree.root_mut()
+            .unwrap()
+            .child_mut(1)
+            .unwrap()
+            .remove_child_value(0);
+        tree.root_mut()
+            .unwrap()
+            .child_mut(1)
+            .unwrap()
+            .set_child_value(1, 1);
+        assert_eq!(
+            tree.find_duplicate_subtrees(),
+            vec![vec![vec![0, 0], vec![1, 1]]]
+        );
+
+        assert!(sample_tree().find_duplicate_subtrees().is_empty());
+    }
+
+    #[test]
+    fn flat_indexes() {
+        let tree = EytzingerTree::<u32>::new(2);
+
+        assert_eq!(tree.child_flat_index(0, 1), 2);
+        assert_eq!(tree.child_flat_index(2, 0), 5);
+        assert_eq!(tree.parent_flat_index(2), Some(0));
+        assert_eq!(tree.parent_flat_index(5), Some(2));
+        assert_eq!(tree.parent_flat_index(0), None);
+    }
+
+    #[test]
+    fn depth_slot_ranges() {
+        let tree = EytzingerTree::<u32>::new(2);
+        assert_eq!(tree.depth_slot_ranges(3), vec![0..1, 1..3, 3..7, 7..15]);
+        assert_eq!(tree.depth_slot_ranges(0), vec![0..1]);
+
+        let tree = EytzingerTree::<u32>::mixed_arity(vec![3, 2]);
+        assert_eq!(tree.depth_slot_ranges(2), vec![0..1, 1..4, 4..10]);
+    }
+
+    #[test]
+    fn update_root() {
+        let mut tree = sample_tree();
+
+        assert!(tree.update_root(|value| *value *= 10));
+
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![50, 2, 7, 1, 4, 8, 3]
+        );
+        assert!(tree.is_consistent());
+
+        let mut empty = EytzingerTree::<u32>::new(2);
+        assert!(!empty.update_root(|_| panic!("there is no root")));
+    }
+
+    #[test]
+    fn set_root_value_keeps_children() {
+        let mut tree = sample_tree();
+
+        tree.set_root_value(6);
+
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.node_at_path(&[0, 1, 0]).map(|n| *n.value()), Some(3));
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn recompute_len() {
+        let mut tree = sample_tree();
+        tree.len = 2;
+        assert!(!tree.is_consistent());
+
+        assert_eq!(tree.recompute_len(), 7);
+        assert_eq!(tree.len(), 7);
+        assert!(tree.is_consistent());
+
+        let mut empty = EytzingerTree::<u32>::new(2);
+        empty.len = 1;
+        assert_eq!(empty.recompute_len(), 0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn get() {
+        let mut tree = sample_tree();
+
+        assert_eq!(tree.get(0), Some(&5));
+        assert_eq!(tree.get(9), Some(&3));
+        assert_eq!(tree.get(5), None);
+        assert_eq!(tree.get(1000), None);
+
+        *tree.get_mut(6).unwrap() += 1;
+        assert_eq!(tree.node_at_path(&[1, 1]).map(|n| *n.value()), Some(9));
+        assert_eq!(tree.get_mut(5), None);
+        assert_eq!(tree.get_mut(1000), None);
+    }
+
+    #[test]
+    fn child_count_histogram() {
+        assert_eq!(sample_tree().child_count_histogram(), vec![3, 2, 2]);
+        assert_eq!(
+            EytzingerTree::<u32>::new(3).child_count_histogram(),
+            vec![0; 4]
+        );
+
+        let tree = EytzingerTree::perfect_from_fn(3, 1, |_| ());
+        assert_eq!(tree.child_count_histogram(), vec![3, 0, 0, 1]);
+    }
+
+    #[test]
+    fn complete_subtrees_of_depth() {
+        let mut tree = sample_tree();
+        let values_of =
+            |nodes: Vec<Node<'_, u32>>| -> Vec<_> { nodes.iter().map(|n| *n.value()).collect() };
+
+        assert_eq!(values_of(tree.complete_subtrees_of_depth(0)), vec![1, 8, 3]);
+        assert!(tree.complete_subtrees_of_depth(1).is_empty());
+
+        tree.entry_mut(&[0, 1, 1]).unwrap().or_insert(6);
+        assert_eq!(values_of(tree.complete_subtrees_of_depth(1)), vec![4]);
+        assert!(tree.complete_subtrees_of_depth(2).is_empty());
+
+        tree.entry_mut(&[0, 0, 0]).unwrap().or_insert(0);
+        tree.entry_mut(&[0, 0, 1]).unwrap().or_insert(0);
+        assert_eq!(values_of(tree.complete_subtrees_of_depth(1)), vec![1, 4]);
+        assert_eq!(values_of(tree.complete_subtrees_of_depth(2)), vec![2]);
+
+        assert!(EytzingerTree::<u32>::new(2)
+            .complete_subtrees_of_depth(0)
+            .is_empty());
+    }
+
+    #[test]
+    fn diameter() {
+        assert_eq!(sample_tree().diameter(), 5);
+        assert_eq!(EytzingerTree::<u32>::new(2).diameter(), 0);
+
+        let mut tree = EytzingerTree::new(2);
+        {
+            let mut root = tree.set_root_value(0);
+            let mut left = root.set_child_value(0, 1);
+            left.set_child_value(0, 2).set_child_value(0, 3);
+            left.set_child_value(1, 4).set_child_value(1, 5);
+        }
+        assert_eq!(tree.height(), Some(3));
+        assert_eq!(tree.diameter(), 4);
+
+        tree.root_mut().unwrap().set_child_value(1, 6);
+        assert_eq!(tree.diameter(), 4);
+    }
+
+    #[test]
+    fn set_value_at_high_index() {
+        let mut tree = EytzingerTree::new(1000);
+        {
+            let mut root = tree.set_root_value(0);
+            let mut child = root.set_child_value(999, 1);
+            child.set_child_value(999, 2);
+        }
+
+        let index = tree.child_flat_index(tree.child_flat_index(0, 999), 999);
+        assert_eq!(index, 1_001_000);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.get(index), Some(&2));
+        assert_eq!(tree.node_at_path(&[999, 999]).map(|n| *n.value()), Some(2));
+        assert_eq!(tree.nodes.len(), index + 1);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn set_value_large_jumps() {
+        let mut tree = EytzingerTree::new(2);
+        let mut node = tree.set_root_value(0);
+        for depth in 1..=20 {
+            node = node.to_child_entry(1).or_insert(depth);
+        }
+
+        assert_eq!(tree.len(), 21);
+        assert_eq!(tree.nodes.len(), (1 << 21) - 1);
+        assert_eq!(tree.deepest_node().map(|n| *n.value()), Some(20));
+    }
+
+    #[test]
+    fn density() {
+        let mut tree = EytzingerTree::new(2);
+        assert_eq!(tree.density(), 1.0);
+
+        tree.set_root_value(1).set_child_value(1, 2);
+        assert_eq!(tree.density(), 2.0 / 3.0);
+    }
+
+    #[test]
+    fn vacant_slot_count() {
+        let mut tree = sample_tree();
+
+        // the slots for the children of 1 and the left child of 7 are vacant
+        assert_eq!(tree.vacant_slot_count(), 3);
+        assert_eq!(
+            tree.vacant_slot_count(),
+            tree.nodes.iter().filter(|slot| slot.is_none()).count()
+        );
+
+        tree.split_off(&[0, 1]);
+        assert_eq!(tree.vacant_slot_count(), 5);
+
+        tree.truncate_unused();
+        assert_eq!(tree.vacant_slot_count(), 2);
+    }
+
+    #[test]
+    fn iter_paths() {
+        let tree = sample_tree();
+
+        let paths: Vec<_> = tree.iter_paths().collect();
+
+        assert_eq!(paths.len(), 7);
+        assert_eq!(paths[0], (vec![], &5));
+        assert_eq!(paths[6], (vec![0, 1, 0], &3));
+
+        for (path, value) in paths {
+            assert_eq!(tree.node_at_path(&path).map(|n| n.value()), Some(value));
+        }
+    }
+
+    #[test]
+    fn into_path_pairs() {
+        let pairs = sample_tree().into_path_pairs();
+
+        assert_eq!(pairs.len(), 7);
+        assert_eq!(pairs[0], (vec![], 5));
+        assert_eq!(pairs[6], (vec![0, 1, 0], 3));
+        assert_eq!(EytzingerTree::from_paths(2, pairs), Ok(sample_tree()));
+    }
+
+    #[test]
+    fn from_paths_round_trips() {
+        let tree = sample_tree();
+
+        let mut pairs: Vec<_> = tree
+            .iter_paths()
+            .map(|(path, &value)| (path, value))
+            .collect();
+        pairs.reverse();
+
+        assert_eq!(EytzingerTree::from_paths(2, pairs), Ok(tree));
+    }
+
+    #[test]
+    fn cmp_ignores_capacity() {
+        let tree = sample_tree();
+
+        let mut padded = sample_tree();
+        padded
+            .root_mut()
+            .unwrap()
+            .set_child_value(1, 7)
+            .set_child_value(0, 6)
+            .set_child_value(1, 9);
+        padded.split_off(&[1, 0]);
+
+        assert!(padded.nodes.len() > tree.nodes.len());
+        assert_eq!(padded.cmp(&tree), Ordering::Equal);
+    }
+
+    #[cfg(all(feature = "tracing", feature = "std"))]
+    #[test]
+    fn mutation_with_tracing() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+        use tracing::{span, Event, Metadata, Subscriber};
+
+        struct CountingSubscriber(Arc<AtomicUsize>);
+
+        impl Subscriber for CountingSubscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+            fn event(&self, _: &Event<'_>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn enter(&self, _: &span::Id) {}
+
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let events = Arc::new(AtomicUsize::new(0));
+        let subscriber = CountingSubscriber(events.clone());
+
+        let tree = tracing::subscriber::with_default(subscriber, || {
+            let mut tree = sample_tree();
+            tree.root_mut().unwrap().remove_child_value(0);
+            tree
+        });
+
+        assert_eq!(tree.len(), 3);
+        assert!(events.load(Ordering::SeqCst) > 0);
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    #[test]
+    fn removal_logs_removed_count() {
+        use log::{LevelFilter, Log, Metadata, Record};
+        use std::sync::Mutex;
+
+        struct CapturingLogger(Mutex<Vec<String>>);
+
+        impl Log for CapturingLogger {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record<'_>) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: CapturingLogger = CapturingLogger(Mutex::new(Vec::new()));
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+
+        let mut tree = sample_tree();
+        tree.root_mut().unwrap().remove_child_value(0);
+
+        let messages = LOGGER.0.lock().unwrap();
+        assert!(messages
+            .iter()
+            .any(|message| message == "removed 4 nodes from the subtree at index 1"));
+    }
+
+    #[test]
+    fn hash_ignores_capacity() {
+        let tree = sample_tree();
+
+        let mut padded = sample_tree();
+        padded
+            .root_mut()
+            .unwrap()
+            .set_child_value(1, 7)
+            .set_child_value(0, 6)
+            .set_child_value(1, 9);
+        padded.split_off(&[1, 0]);
+
+        assert!(padded.nodes.len() > tree.nodes.len());
+        assert_eq!(hash_of(&padded), hash_of(&tree));
+        assert_ne!(hash_of(&padded), hash_of(&EytzingerTree::<u32>::new(2)));
+    }
+
+    #[test]
+    fn cmp_orders_by_paths_then_values() {
+        let tree = sample_tree();
+
+        let mut larger_value = sample_tree();
+        *larger_value.root_mut().unwrap().child_value_mut(1).unwrap() = 10;
+        assert_eq!(tree.cmp(&larger_value), Ordering::Less);
+
+        // the path [0, 0] sorts before the path [1] so removing it makes the tree compare greater
+        let mut fewer_nodes = sample_tree();
+        fewer_nodes.split_off(&[0, 0]);
+        assert_eq!(tree.cmp(&fewer_nodes), Ordering::Less);
+
+        assert_eq!(tree.cmp(&EytzingerTree::new(3)), Ordering::Less);
+    }
+
+    #[test]
+    fn from_paths_errors() {
+        assert_eq!(
+            EytzingerTree::from_paths(2, vec![(vec![], 5), (vec![0, 1], 4)]),
+            Err(PathError::MissingParent { path: vec![0, 1] })
+        );
+        assert_eq!(
+            EytzingerTree::from_paths(2, vec![(vec![], 5), (vec![2], 4)]),
+            Err(PathError::InvalidPath { path: vec![2] })
+        );
+        assert_eq!(
+            EytzingerTree::from_paths(0, vec![(vec![], 5)]),
+            Err(PathError::InvalidArity)
+        );
+    }
+
+    #[test]
+    fn from_paths_too_large() {
+        let chain = |depth: usize| (0..=depth).map(|length| (vec![1; length], length));
+
+        // the index of the node at depth 62 can be represented but not allocated
+        assert_eq!(
+            EytzingerTree::from_paths(2, chain(62)),
+            Err(PathError::TooLarge { path: vec![1; 62] })
+        );
+
+        let tree = EytzingerTree::from_paths(2, chain(16)).unwrap();
+        assert_eq!(tree.len(), 17);
+    }
+
+    #[test]
+    fn try_for_each_stops_at_error() {
+        let tree = sample_tree();
+
+        let mut visited = vec![];
+        let result = tree.try_for_each(|&value| {
+            visited.push(value);
+            if value == 4 {
+                Err(value)
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(result, Err(4));
+        assert_eq!(visited, vec![5, 2, 7, 1, 4]);
+    }
+
+    #[test]
+    fn into_breadth_first_iterator_returns_breadth_first() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            {
+                let mut left = root.set_child_value(0, 2);
+
+                left.set_child_value(0, 1);
+                let mut left_right = left.set_child_value(1, 4);
+                left_right.set_child_value(0, 3);
+            }
+            {
+                let mut right = root.set_child_value(1, 7);
+                right.set_child_value(1, 8);
+            }
+        }
+
+        assert_eq!(tree.len(), 7);
+
+        let breadth_first: Vec<_> = tree.into_breadth_first_iterator().collect();
+
+        assert_eq!(breadth_first, vec![5, 2, 7, 1, 4, 8, 3]);
+    }
+
+    #[test]
+    fn height() {
+        assert_eq!(EytzingerTree::<u32>::new(2).height(), None);
+        assert_eq!(sample_tree().height(), Some(3));
     }
 
-    fn child_entry(&mut self, parent: usize, child: usize) -> Entry<N> {
-        let child_index = self.child_index(parent, child);
-        self.entry(child_index)
+    #[test]
+    fn subtree_aggregates_sum() {
+        let tree = sample_tree();
+
+        let sums = tree
+            .subtree_aggregates(|&value, children: &[u32]| value + children.iter().sum::<u32>());
+
+        assert_eq!(sums.len(), 10);
+        assert_eq!(sums[0], Some(30));
+        assert_eq!(sums[1], Some(10));
+        assert_eq!(sums[2], Some(15));
+        assert_eq!(sums[4], Some(7));
+        assert_eq!(sums[5], None);
     }
 
-    fn value(&self, index: usize) -> Option<&Option<N>> {
-        self.nodes.get(index)
+    #[test]
+    fn deepest_node() {
+        let tree = sample_tree();
+
+        let deepest = tree.deepest_node().unwrap();
+        assert_eq!(deepest.value(), &3);
+        assert_eq!(tree.index_calculator.depth(deepest.index()), 3);
+
+        assert!(EytzingerTree::<u32>::new(2).deepest_node().is_none());
+
+        let mut tree = EytzingerTree::new(2);
+        tree.set_root_value(1).set_children(vec![Some(2), Some(3)]);
+        assert_eq!(tree.deepest_node().map(|n| *n.value()), Some(2));
     }
 
-    fn value_mut(&mut self, index: usize) -> Option<&mut Option<N>> {
-        self.nodes.get_mut(index)
+    #[test]
+    fn count_at_depth() {
+        let tree = sample_tree();
+
+        assert_eq!(tree.count_at_depth(0), 1);
+        assert_eq!(tree.count_at_depth(1), 2);
+        assert_eq!(tree.count_at_depth(2), 3);
+        assert_eq!(tree.count_at_depth(3), 1);
+        assert_eq!(tree.count_at_depth(4), 0);
+        assert_eq!(tree.count_at_depth(1000), 0);
     }
 
-    fn parent(&self, child: usize) -> Option<Node<N>> {
-        let parent_index = self.parent_index(child)?;
-        self.node(parent_index)
+    #[test]
+    fn widest_level() {
+        assert_eq!(sample_tree().widest_level(), Some((2, 3)));
+        assert_eq!(EytzingerTree::<u32>::new(2).widest_level(), None);
     }
 
-    fn parent_mut(&mut self, child: usize) -> Result<NodeMut<N>, &mut Self> {
-        if let Some(parent_index) = self.parent_index(child) {
-            self.node_mut(parent_index)
-        } else {
-            Err(self)
+    #[test]
+    fn widest_level_prefers_shallowest() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            root.set_child_value(0, 2).set_child_value(0, 1);
+            root.set_child_value(1, 7).set_child_value(1, 8);
         }
-    }
 
-    fn child(&self, parent: usize, child: usize) -> Option<Node<N>> {
-        let child_index = self.child_index(parent, child);
-        self.node(child_index)
+        assert_eq!(tree.widest_level(), Some((1, 2)));
     }
 
-    fn child_mut(&mut self, parent: usize, child: usize) -> Result<NodeMut<N>, &mut Self> {
-        let child_index = self.child_index(parent, child);
-        self.node_mut(child_index)
+    #[test]
+    fn is_balanced_for_sample_tree() {
+        assert!(sample_tree().is_balanced());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{DepthFirstOrder, EytzingerTree};
-    use matches::assert_matches;
+    #[test]
+    fn is_balanced_for_empty_tree() {
+        assert!(EytzingerTree::<u32>::new(2).is_balanced());
+    }
 
     #[test]
-    fn root_is_none_for_empty() {
+    fn is_balanced_false_for_chain() {
         let mut tree = EytzingerTree::<u32>::new(2);
+        tree.set_root_value(1)
+            .to_child_entry(1)
+            .or_insert(2)
+            .to_child_entry(1)
+            .or_insert(3);
 
-        assert_matches!(tree.root(), None);
-        assert_matches!(tree.root_mut(), None);
+        assert!(!tree.is_balanced());
     }
 
     #[test]
-    fn set_root_value_sets_root() {
-        let mut tree = EytzingerTree::<u32>::new(2);
-
-        let expected_root = 5;
-        tree.set_root_value(expected_root);
+    fn is_balanced_false_for_unbalanced_subtree() {
+        let mut tree = sample_tree();
+        tree.root_mut()
+            .unwrap()
+            .to_child(1)
+            .ok()
+            .unwrap()
+            .remove_child_value(1);
 
-        assert_eq!(tree.root().map(|x| *x.value()).unwrap(), expected_root);
-        assert_eq!(tree.root_mut().map(|x| *x.value()).unwrap(), expected_root);
+        assert!(!tree.is_balanced());
     }
 
     #[test]
-    fn depth_first_iter_returns_empty_for_empty_tree() {
-        let tree = EytzingerTree::<u32>::new(2);
+    fn is_complete_for_complete_tree() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        {
+            let mut root = tree.set_root_value(5);
+            root.set_child_value(1, 7);
+            let mut left = root.set_child_value(0, 2);
+            left.set_child_value(0, 1);
+            left.set_child_value(1, 4);
+        }
 
-        assert_matches!(
-            tree.depth_first_iter(DepthFirstOrder::PostOrder).next(),
-            None
-        )
+        assert!(tree.is_complete());
     }
 
     #[test]
-    fn depth_first_iter_returns_depth_first() {
+    fn is_complete_false_for_sample_tree() {
+        assert!(!sample_tree().is_complete());
+    }
+
+    #[test]
+    fn is_perfect_for_perfect_tree() {
         let mut tree = EytzingerTree::<u32>::new(2);
         {
             let mut root = tree.set_root_value(5);
             {
                 let mut left = root.set_child_value(0, 2);
-
                 left.set_child_value(0, 1);
-                let mut left_right = left.set_child_value(1, 4);
-                left_right.set_child_value(0, 3);
+                left.set_child_value(1, 4);
             }
             {
                 let mut right = root.set_child_value(1, 7);
+                right.set_child_value(0, 6);
                 right.set_child_value(1, 8);
             }
         }
 
+        assert!(tree.is_perfect());
+    }
+
+    #[test]
+    fn is_perfect_false_for_sample_tree() {
+        assert!(!sample_tree().is_perfect());
+    }
+
+    #[test]
+    fn perfect_from_fn() {
+        let tree = EytzingerTree::perfect_from_fn(2, 2, |path| path.len());
+
         assert_eq!(tree.len(), 7);
+        assert!(tree.is_perfect());
 
-        let depth_first: Vec<_> = tree
-            .depth_first_iter(DepthFirstOrder::PreOrder)
+        let breadth_first: Vec<_> = tree
+            .breadth_first_iter()
             .map(|n| n.value())
             .cloned()
             .collect();
 
-        assert_eq!(depth_first, vec![5, 2, 1, 4, 3, 7, 8]);
+        assert_eq!(breadth_first, vec![0, 1, 1, 2, 2, 2, 2]);
+    }
 
-        let depth_first: Vec<_> = tree
-            .depth_first_iter(DepthFirstOrder::PostOrder)
+    #[test]
+    fn copy_subtree() {
+        let mut tree = sample_tree();
+
+        tree.copy_subtree(&[0], &[1], 0).unwrap();
+
+        assert_eq!(tree.len(), 11);
+
+        let right_breadth_first: Vec<_> = tree
+            .root()
+            .unwrap()
+            .child(1)
+            .unwrap()
+            .breadth_first_iter()
             .map(|n| n.value())
             .cloned()
             .collect();
 
-        assert_eq!(depth_first, vec![1, 3, 4, 2, 8, 7, 5]);
+        assert_eq!(right_breadth_first, vec![7, 2, 8, 1, 4, 3]);
+
+        let left_breadth_first: Vec<_> = tree
+            .root()
+            .unwrap()
+            .child(0)
+            .unwrap()
+            .breadth_first_iter()
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(left_breadth_first, vec![2, 1, 4, 3]);
     }
 
     #[test]
-    fn into_depth_first_iterator_pre_order() {
-        let mut tree = EytzingerTree::<u32>::new(2);
-        {
-            let mut root = tree.set_root_value(5);
-            {
-                let mut left = root.set_child_value(0, 2);
+    fn copy_subtree_errors() {
+        let mut tree = sample_tree();
 
-                left.set_child_value(0, 1);
-                let mut left_right = left.set_child_value(1, 4);
-                left_right.set_child_value(0, 3);
-            }
-            {
-                let mut right = root.set_child_value(1, 7);
-                right.set_child_value(1, 8);
-            }
-        }
+        assert_eq!(
+            tree.copy_subtree(&[1, 0], &[0], 0),
+            Err(CopyError::VacantSource)
+        );
+        assert_eq!(
+            tree.copy_subtree(&[0], &[1, 0], 0),
+            Err(CopyError::VacantDestinationParent)
+        );
+        assert_eq!(
+            tree.copy_subtree(&[0], &[1], 1),
+            Err(CopyError::OccupiedDestination)
+        );
+        assert_eq!(
+            tree.copy_subtree(&[0], &[0, 1], 1),
+            Err(CopyError::DestinationInSource)
+        );
+        assert_eq!(tree, sample_tree());
+    }
 
-        assert_eq!(tree.len(), 7);
+    #[test]
+    fn copy_subtree_with_policies() {
+        let right_values = |tree: &EytzingerTree<u32>| -> Vec<_> {
+            tree.node_at_path(&[1])
+                .unwrap()
+                .breadth_first_iter()
+                .map(|n| *n.value())
+                .collect()
+        };
 
-        let depth_first: Vec<_> = tree
-            .into_depth_first_iterator(DepthFirstOrder::PreOrder)
+        let mut tree = sample_tree();
+        assert_eq!(
+            tree.copy_subtree_with(&[0], &[], 1, OverwritePolicy::Error),
+            Err(CopyError::OccupiedDestination)
+        );
+        assert_eq!(tree, sample_tree());
+
+        let mut tree = sample_tree();
+        tree.copy_subtree_with(&[0], &[], 1, OverwritePolicy::Overwrite)
+            .unwrap();
+        assert_eq!(right_values(&tree), vec![2, 1, 4, 3]);
+        assert_eq!(tree.len(), 9);
+        assert!(tree.is_consistent());
+
+        let mut tree = sample_tree();
+        assert_eq!(
+            tree.copy_subtree_with(&[0], &[], 1, OverwritePolicy::Merge),
+            Err(CopyError::Collision)
+        );
+        assert_eq!(tree, sample_tree());
+
+        tree.copy_subtree_with(&[0, 1], &[], 1, OverwritePolicy::Merge)
+            .unwrap();
+        assert_eq!(right_values(&tree), vec![7, 3, 8]);
+        assert_eq!(tree.len(), 8);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn copy_subtree_with_overwrite_rejects_source() {
+        let mut tree = sample_tree();
+
+        assert_eq!(
+            tree.copy_subtree_with(&[0], &[], 0, OverwritePolicy::Overwrite),
+            Err(CopyError::DestinationInSource)
+        );
+        assert_eq!(tree, sample_tree());
+    }
+
+    #[test]
+    fn copy_subtree_with_overwrite_replaces_ancestor() {
+        let mut tree = sample_tree();
+
+        tree.copy_subtree_with(&[0, 1], &[], 0, OverwritePolicy::Overwrite)
+            .unwrap();
+
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![5, 4, 7, 3, 8]
+        );
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn sift_up() {
+        let mut tree = EytzingerTree::from_complete_vec(2, vec![9, 7, 8, 3, 6, 5, 4]);
+        tree.entry_mut(&[0, 0, 0]).unwrap().or_insert(10);
+
+        tree.sift_up(&[0, 0, 0]);
+
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![10, 9, 8, 7, 6, 5, 4, 3]
+        );
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn sift_down() {
+        let mut tree = EytzingerTree::from_complete_vec(2, vec![1, 9, 8, 7, 6, 5]);
+
+        tree.sift_down(&[]);
+
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![9, 7, 8, 1, 6, 5]
+        );
+
+        tree.sift_down(&[0]);
+        assert_eq!(tree.node_at_path(&[0]).map(|n| *n.value()), Some(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "operation requires a complete tree")]
+    fn sift_up_requires_complete_tree() {
+        sample_tree().sift_up(&[0, 1, 0]);
+    }
+
+    #[test]
+    fn swap_values() {
+        let mut tree = sample_tree();
+
+        tree.swap_values(&[], &[0, 1, 0]).unwrap();
+
+        let breadth_first: Vec<_> = tree
+            .breadth_first_iter()
+            .map(|n| n.value())
+            .cloned()
             .collect();
 
-        assert_eq!(depth_first, vec![5, 2, 1, 4, 3, 7, 8]);
+        assert_eq!(breadth_first, vec![3, 2, 7, 1, 4, 8, 5]);
     }
 
     #[test]
-    fn into_depth_first_iterator_post_order() {
-        let mut tree = EytzingerTree::<u32>::new(2);
-        {
-            let mut root = tree.set_root_value(5);
-            {
-                let mut left = root.set_child_value(0, 2);
+    fn swap_values_vacant() {
+        let mut tree = sample_tree();
 
-                left.set_child_value(0, 1);
-                let mut left_right = left.set_child_value(1, 4);
-                left_right.set_child_value(0, 3);
-            }
-            {
-                let mut right = root.set_child_value(1, 7);
-                right.set_child_value(1, 8);
-            }
-        }
+        assert_eq!(
+            tree.swap_values(&[], &[1, 0]),
+            Err(SwapError::Vacant { path: vec![1, 0] })
+        );
+        assert_eq!(tree, sample_tree());
+    }
 
-        assert_eq!(tree.len(), 7);
+    #[test]
+    fn split_off() {
+        let mut tree = sample_tree();
 
-        let depth_first: Vec<_> = tree
-            .into_depth_first_iterator(DepthFirstOrder::PostOrder)
+        let split_off = tree.split_off(&[1]).unwrap();
+
+        let split_off_breadth_first: Vec<_> = split_off
+            .breadth_first_iter()
+            .map(|n| n.value())
+            .cloned()
             .collect();
 
-        assert_eq!(depth_first, vec![1, 3, 4, 2, 8, 7, 5]);
+        assert_eq!(split_off_breadth_first, vec![7, 8]);
+        assert_eq!(split_off.max_children_per_node(), 2);
+        assert_eq!(tree.len(), 5);
+        assert_matches!(tree.root().unwrap().child(1), None);
     }
 
     #[test]
-    fn breadth_first_iter_returns_empty_for_empty_tree() {
-        let tree = EytzingerTree::<u32>::new(2);
+    fn split_off_vacant_returns_none() {
+        let mut tree = sample_tree();
 
-        assert_matches!(tree.breadth_first_iter().next(), None)
+        assert_matches!(tree.split_off(&[1, 0]), None);
+        assert_eq!(tree, sample_tree());
     }
 
     #[test]
-    fn breadth_first_iter_returns_breadth_first() {
+    fn balance_bst() {
         let mut tree = EytzingerTree::<u32>::new(2);
         {
-            let mut root = tree.set_root_value(5);
-            {
-                let mut left = root.set_child_value(0, 2);
-
-                left.set_child_value(0, 1);
-                let mut left_right = left.set_child_value(1, 4);
-                left_right.set_child_value(0, 3);
-            }
-            {
-                let mut right = root.set_child_value(1, 7);
-                right.set_child_value(1, 8);
+            let mut node = tree.set_root_value(1);
+            for value in 2..=7 {
+                node = node.to_child_entry(1).or_insert(value);
             }
         }
 
+        tree.balance_bst();
+
         assert_eq!(tree.len(), 7);
+        assert_eq!(tree.height(), Some(2));
+        assert!(tree.is_perfect());
 
-        let breadth_first: Vec<_> = tree
-            .breadth_first_iter()
-            .map(|n| n.value())
-            .cloned()
+        let in_order: Vec<_> = tree
+            .in_order_indexes()
+            .into_iter()
+            .map(|index| *tree.node(index).unwrap().value())
             .collect();
+        assert_eq!(in_order, vec![1, 2, 3, 4, 5, 6, 7]);
+    }
 
-        assert_eq!(breadth_first, vec![5, 2, 7, 1, 4, 8, 3]);
+    #[test]
+    #[should_panic(expected = "operation requires max_children_per_node == 2, got 3")]
+    fn balance_bst_requires_binary_tree() {
+        EytzingerTree::<u32>::new(3).balance_bst();
     }
 
     #[test]
-    fn into_breadth_first_iterator_returns_breadth_first() {
+    fn to_sorted_vec() {
+        let tree = bst_tree(&[5, 2, 7, 1, 4]);
+
+        assert_eq!(tree.to_sorted_vec(), vec![1, 2, 4, 5, 7]);
+    }
+
+    #[test]
+    fn enumerate_slots() {
+        let tree = sample_tree();
+
+        let slots: Vec<_> = tree.enumerate_slots().collect();
+        assert_eq!(slots.len(), 7);
+        assert_eq!(slots[0], (0, &5));
+        assert_eq!(
+            slots,
+            vec![
+                (0, &5),
+                (1, &2),
+                (2, &7),
+                (3, &1),
+                (4, &4),
+                (6, &8),
+                (9, &3)
+            ]
+        );
+        assert_eq!(EytzingerTree::<u32>::new(2).enumerate_slots().count(), 0);
+    }
+
+    #[test]
+    fn is_valid_bst() {
+        let mut tree = bst_tree(&[5, 2, 7, 1, 4, 3, 8]);
+        assert!(tree.is_valid_bst());
+        assert!(EytzingerTree::<u32>::new(2).is_valid_bst());
+
+        tree.swap_values(&[0, 0], &[0, 1]).unwrap();
+        assert!(!tree.is_valid_bst());
+    }
+
+    #[test]
+    fn is_valid_bst_rejects_duplicates() {
+        assert!(!bst_tree(&[5, 5]).is_valid_bst());
+    }
+
+    #[test]
+    #[should_panic(expected = "operation requires max_children_per_node == 2, got 3")]
+    fn is_valid_bst_requires_binary_tree() {
+        EytzingerTree::<u32>::new(3).is_valid_bst();
+    }
+
+    #[test]
+    fn occupancy_is_consistent_after_mutations() {
+        let mut tree = EytzingerTree::perfect_from_fn(3, 5, |path| path.len() as u32);
+        assert!(tree.is_consistent());
+
+        tree.split_off(&[1, 2]).unwrap();
+        assert!(tree.is_consistent());
+
+        tree.copy_subtree(&[0, 0], &[1], 2).unwrap();
+        assert!(tree.is_consistent());
+
+        tree.root_mut().unwrap().to_child(2).ok().unwrap().remove();
+        assert!(tree.is_consistent());
+
+        let mut root = tree.root_mut().unwrap();
+        let mut node = root.child_entry(2).or_insert(7);
+        for value in 0..4 {
+            node = node.to_child_entry(2).or_insert(value);
+        }
+        assert!(tree.is_consistent());
+
+        let occupied: Vec<_> = tree
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|value| (index, value)))
+            .collect();
+        assert_eq!(tree.enumerate_values().collect::<Vec<_>>(), occupied);
+        assert_eq!(tree.breadth_first_iter().count(), occupied.len());
+
+        tree.clear();
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn occupancy_is_consistent_for_balance_bst() {
+        let mut tree = bst_tree(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        tree.balance_bst();
+
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    #[should_panic(expected = "operation requires max_children_per_node == 2, got 4")]
+    fn to_sorted_vec_requires_binary_tree() {
+        EytzingerTree::<u32>::new(4).to_sorted_vec();
+    }
+
+    #[test]
+    #[should_panic(expected = "the child index 2 should be less than max_children_per_node (2)")]
+    fn set_child_value_out_of_range_panics() {
         let mut tree = EytzingerTree::<u32>::new(2);
-        {
-            let mut root = tree.set_root_value(5);
-            {
-                let mut left = root.set_child_value(0, 2);
+        tree.set_root_value(5).set_child_value(2, 3);
+    }
 
-                left.set_child_value(0, 1);
-                let mut left_right = left.set_child_value(1, 4);
-                left_right.set_child_value(0, 3);
-            }
-            {
-                let mut right = root.set_child_value(1, 7);
-                right.set_child_value(1, 8);
+    #[test]
+    fn remove_deep_chain() {
+        let mut tree = EytzingerTree::<u32>::new(1);
+        {
+            let mut node = tree.set_root_value(0);
+            for value in 1..100_000 {
+                node = node.to_child_entry(0).or_insert(value);
             }
         }
 
-        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.len(), 100_000);
 
-        let breadth_first: Vec<_> = tree.into_breadth_first_iterator().collect();
+        let (value, _) = tree.root_mut().unwrap().remove();
 
-        assert_eq!(breadth_first, vec![5, 2, 7, 1, 4, 8, 3]);
+        assert_eq!(value, 0);
+        assert!(tree.is_empty());
+        assert_matches!(tree.root(), None);
     }
 }
@@ -0,0 +1,195 @@
+use crate::{entry::Entry, EytzingerTree};
+use alloc::vec::Vec;
+
+/// An Eytzinger tree with an optional metadata value for each node, this allows nodes to be
+/// annotated without changing their values. The metadata of a node is cleared whenever the node
+/// is set or removed through this wrapper.
+///
+/// This is created with `EytzingerTree::with_metadata`.
+#[derive(Debug, Clone)]
+pub struct MetadataTree<N, D> {
+    tree: EytzingerTree<N>,
+    metadata: Vec<Option<D>>,
+}
+
+impl<N> EytzingerTree<N> {
+    /// Wraps the tree so metadata may be stored for each node, every node starts without any
+    /// metadata.
+    pub fn with_metadata<D>(self) -> MetadataTree<N, D> {
+        MetadataTree {
+            tree: self,
+            metadata: Vec::new(),
+        }
+    }
+}
+
+impl<N, D> MetadataTree<N, D> {
+    /// Gets the Eytzinger tree.
+    pub fn tree(&self) -> &EytzingerTree<N> {
+        &self.tree
+    }
+
+    /// Unwraps the Eytzinger tree, dropping all metadata.
+    pub fn into_tree(self) -> EytzingerTree<N> {
+        self.tree
+    }
+
+    /// Gets the metadata of the node at the specified path of child offsets from the root, `None`
+    /// if there is no node at the path or it has no metadata.
+    pub fn get_meta(&self, path: &[usize]) -> Option<&D> {
+        let index = self.occupied_index(path)?;
+        self.metadata.get(index).and_then(Option::as_ref)
+    }
+
+    /// Sets the metadata of the node at the specified path of child offsets from the root.
+    ///
+    /// # Errors
+    ///
+    /// Returns `meta` if there is no node at the path.
+    ///
+    /// # Returns
+    ///
+    /// The previous metadata of the node if there was any.
+    pub fn set_meta(&mut self, path: &[usize], meta: D) -> Result<Option<D>, D> {
+        let index = match self.occupied_index(path) {
+            Some(index) => index,
+            None => return Err(meta),
+        };
+
+        if self.metadata.len() <= index {
+            self.metadata.resize_with(index + 1, || None);
+        }
+
+        Ok(self.metadata[index].replace(meta))
+    }
+
+    /// Removes the metadata of the node at the specified path of child offsets from the root.
+    ///
+    /// # Returns
+    ///
+    /// The removed metadata or `None` if there is no node at the path or it has no metadata.
+    pub fn remove_meta(&mut self, path: &[usize]) -> Option<D> {
+        let index = self.occupied_index(path)?;
+        self.clear_meta(index)
+    }
+
+    /// Sets the value of the node at the specified path of child offsets from the root, the
+    /// metadata of the node is cleared. The children of an existing node are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `value` if a child offset is out of range or there is no node for the parent of the
+    /// path.
+    ///
+    /// # Returns
+    ///
+    /// The previous value of the node if there was one.
+    pub fn set_value(&mut self, path: &[usize], value: N) -> Result<Option<N>, N> {
+        let index = match self.tree.path_index(path) {
+            Some(index) => index,
+            None => return Err(value),
+        };
+
+        let old_value = match self.tree.entry_mut(path) {
+            Some(Entry::Occupied(mut node)) => Some(node.replace_value(value)),
+            Some(Entry::Vacant(entry)) => {
+                entry.insert(value);
+                None
+            }
+            None => return Err(value),
+        };
+
+        self.clear_meta(index);
+        Ok(old_value)
+    }
+
+    /// Removes the subtree at the specified path of child offsets from the root, the metadata of
+    /// every removed node is cleared.
+    ///
+    /// # Returns
+    ///
+    /// The removed subtree or `None` if there was no node at the path.
+    pub fn remove(&mut self, path: &[usize]) -> Option<EytzingerTree<N>> {
+        let removed_indexes: Vec<_> = self
+            .tree
+            .node_at_path(path)?
+            .breadth_first_iter()
+            .map(|node| node.index)
+            .collect();
+
+        for index in removed_indexes {
+            self.clear_meta(index);
+        }
+
+        self.tree.split_off(path)
+    }
+
+    fn occupied_index(&self, path: &[usize]) -> Option<usize> {
+        self.tree
+            .path_index(path)
+            .filter(|&index| self.tree.node(index).is_some())
+    }
+
+    fn clear_meta(&mut self, index: usize) -> Option<D> {
+        self.metadata.get_mut(index).and_then(Option::take)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::sample_tree;
+
+    #[test]
+    fn set_meta() {
+        let mut tree = sample_tree().with_metadata::<&str>();
+
+        assert_eq!(tree.set_meta(&[0, 1], "four"), Ok(None));
+        assert_eq!(tree.set_meta(&[0, 1], "4"), Ok(Some("four")));
+        assert_eq!(tree.set_meta(&[1, 0], "vacant"), Err("vacant"));
+
+        assert_eq!(tree.get_meta(&[0, 1]), Some(&"4"));
+        assert_eq!(tree.get_meta(&[0]), None);
+        assert_eq!(tree.get_meta(&[1, 0]), None);
+
+        assert_eq!(tree.remove_meta(&[0, 1]), Some("4"));
+        assert_eq!(tree.get_meta(&[0, 1]), None);
+    }
+
+    #[test]
+    fn set_value_clears_meta() {
+        let mut tree = sample_tree().with_metadata::<&str>();
+        tree.set_meta(&[1], "seven").unwrap();
+
+        assert_eq!(tree.set_value(&[1], 9), Ok(Some(7)));
+        assert_eq!(tree.get_meta(&[1]), None);
+        assert_eq!(
+            tree.tree().node_at_path(&[1, 1]).map(|n| *n.value()),
+            Some(8)
+        );
+
+        assert_eq!(tree.set_value(&[1, 0], 6), Ok(None));
+        assert_eq!(tree.set_value(&[0, 0, 0, 0], 6), Err(6));
+        assert_eq!(tree.tree().len(), 8);
+    }
+
+    #[test]
+    fn remove_clears_meta() {
+        let mut tree = sample_tree().with_metadata::<u32>();
+        for path in &[&[][..], &[0], &[0, 1], &[0, 1, 0], &[1]] {
+            tree.set_meta(path, 1).unwrap();
+        }
+
+        let removed = tree.remove(&[0]).unwrap();
+        assert_eq!(removed.len(), 4);
+
+        tree.set_value(&[0], 2).unwrap();
+        tree.set_value(&[0, 1], 4).unwrap();
+        tree.set_value(&[0, 1, 0], 3).unwrap();
+
+        assert_eq!(tree.get_meta(&[0]), None);
+        assert_eq!(tree.get_meta(&[0, 1]), None);
+        assert_eq!(tree.get_meta(&[0, 1, 0]), None);
+        assert_eq!(tree.get_meta(&[]), Some(&1));
+        assert_eq!(tree.get_meta(&[1]), Some(&1));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{Node, NodeChildIter};
+
+/// A view of the nodes around a node: its parent, its children and its siblings.
+#[derive(Debug)]
+pub struct Neighbors<'a, N>
+where
+    N: 'a,
+{
+    node: Node<'a, N>,
+    parent: Option<Node<'a, N>>,
+}
+
+impl<'a, N> Copy for Neighbors<'a, N> {}
+
+impl<'a, N> Clone for Neighbors<'a, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, N> Neighbors<'a, N> {
+    pub(crate) fn new(node: Node<'a, N>) -> Self {
+        Self {
+            node,
+            parent: node.parent(),
+        }
+    }
+
+    /// Gets the node these are the neighbors of.
+    pub fn node(&self) -> Node<'a, N> {
+        self.node
+    }
+
+    /// Gets the parent of the node or `None` if the node is the root.
+    pub fn parent(&self) -> Option<Node<'a, N>> {
+        self.parent
+    }
+
+    /// Gets an iterator over the children of the node.
+    pub fn children(&self) -> NodeChildIter<'a, N> {
+        self.node.child_iter()
+    }
+
+    /// Gets an iterator over the other children of the node's parent, this is empty for the
+    /// root.
+    pub fn siblings(&self) -> impl Iterator<Item = Node<'a, N>> {
+        let node = self.node;
+
+        self.parent
+            .into_iter()
+            .flat_map(|parent| parent.child_iter())
+            .filter(move |sibling| sibling.index() != node.index())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::sample_tree;
+
+    #[test]
+    fn neighbors() {
+        let tree = sample_tree();
+        let neighbors = tree.root().unwrap().child(0).unwrap().neighbors();
+
+        assert_eq!(neighbors.node().value(), &2);
+        assert_eq!(neighbors.parent().map(|n| *n.value()), Some(5));
+
+        let children: Vec<_> = neighbors.children().map(|n| *n.value()).collect();
+        assert_eq!(children, vec![1, 4]);
+
+        let siblings: Vec<_> = neighbors.siblings().map(|n| *n.value()).collect();
+        assert_eq!(siblings, vec![7]);
+
+        let root_neighbors = tree.root().unwrap().neighbors();
+        assert!(root_neighbors.parent().is_none());
+        assert_eq!(root_neighbors.siblings().count(), 0);
+    }
+}
@@ -0,0 +1,307 @@
+use crate::{error::NewickError, EytzingerTree};
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+impl<N> EytzingerTree<N> {
+    /// Parses a tree from the Newick format described in `to_newick`.
+    ///
+    /// A child with neither a value nor children is treated as a vacant child slot, a node with
+    /// children must have a value.
+    ///
+    /// # Errors
+    ///
+    /// * `NewickError::UnexpectedToken` if the input is not valid Newick.
+    /// * `NewickError::InvalidValue` if a value could not be parsed.
+    /// * `NewickError::TooManyChildren` if a node has more than `max_children_per_node` children.
+    /// * `NewickError::TooDeep` if a node is nested too deeply to be stored, either its index can
+    ///   not be represented by a `usize` or the storage for it could not be allocated.
+    /// * `NewickError::InvalidArity` if `max_children_per_node` is 0.
+    pub fn from_newick(newick: &str, max_children_per_node: usize) -> Result<Self, NewickError>
+    where
+        N: FromStr,
+    {
+        if max_children_per_node == 0 {
+            return Err(NewickError::InvalidArity);
+        }
+
+        let mut tree = EytzingerTree::new(max_children_per_node);
+
+        // the parents of the current node along with the offset of the current node
+        let mut parents: Vec<(usize, usize)> = vec![];
+        let mut current = 0;
+        let mut has_children = false;
+
+        let mut chars = newick.char_indices().peekable();
+
+        let child_index = |tree: &Self, parent, child_offset, pos| {
+            tree.index_calculator
+                .try_child_index(parent, child_offset)
+                .map_err(|_| NewickError::TooDeep { pos })
+        };
+
+        loop {
+            if !has_children {
+                if let Some(&(pos, '(')) = chars.peek() {
+                    chars.next();
+                    parents.push((current, 0));
+                    current = child_index(&tree, current, 0, pos)?;
+                    continue;
+                }
+            }
+
+            let label_start = chars.peek().map_or(newick.len(), |&(pos, _)| pos);
+            while let Some(&(_, c)) = chars.peek() {
+                if let '(' | ')' | ',' | ';' = c {
+                    break;
+                }
+                chars.next();
+            }
+            let label_end = chars.peek().map_or(newick.len(), |&(pos, _)| pos);
+            let label = &newick[label_start..label_end];
+
+            if has_children || !label.is_empty() {
+                let value = label
+                    .parse()
+                    .map_err(|_| NewickError::InvalidValue { pos: label_end })?;
+                tree.try_set_value(current, value)
+                    .map_err(|_| NewickError::TooDeep { pos: label_start })?;
+            }
+
+            match chars.next() {
+                Some((pos, ',')) => {
+                    let (parent, child_offset) = parents
+                        .last_mut()
+                        .ok_or(NewickError::UnexpectedToken { pos })?;
+
+                    *child_offset += 1;
+                    if *child_offset >= max_children_per_node {
+                        return Err(NewickError::TooManyChildren { pos });
+                    }
+
+                    current = child_index(&tree, *parent, *child_offset, pos)?;
+                    has_children = false;
+                }
+                Some((pos, ')')) => {
+                    let (parent, _) = parents.pop().ok_or(NewickError::UnexpectedToken { pos })?;
+
+                    current = parent;
+                    has_children = true;
+                }
+                Some((pos, ';')) => {
+                    return match chars.find(|(_, c)| !c.is_whitespace()) {
+                        None if parents.is_empty() => Ok(tree),
+                        Some((pos, _)) => Err(NewickError::UnexpectedToken { pos }),
+                        None => Err(NewickError::UnexpectedToken { pos }),
+                    };
+                }
+                Some((pos, _)) => return Err(NewickError::UnexpectedToken { pos }),
+                None => return Err(NewickError::UnexpectedToken { pos: newick.len() }),
+            }
+        }
+    }
+
+    /// Formats the tree using the Newick format.
+    ///
+    /// A leaf is written as just its value. A node with children is written as its children
+    /// separated by commas and enclosed in parentheses, followed by its value. The children of a
+    /// node are written up to and including its last occupied child slot, any vacant child slots
+    /// before that are written as empty. The tree is terminated with a `;`, an empty tree is
+    /// written as just `;`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lz_eytzinger_tree::EytzingerTree;
+    ///
+    /// let mut tree = EytzingerTree::<u32>::new(2);
+    /// {
+    ///     let mut root = tree.set_root_value(5);
+    ///     root.set_child_value(0, 2);
+    ///     root.set_child_value(1, 7).set_child_value(1, 8);
+    /// }
+    ///
+    /// assert_eq!(tree.to_newick(), "(2,(,8)7)5;");
+    /// ```
+    pub fn to_newick(&self) -> String
+    where
+        N: Display,
+    {
+        let mut newick = String::new();
+
+        if self.root().is_some() {
+            let mut pending = vec![(0, 0)];
+            self.open_newick_node(0, &mut newick);
+
+            while let Some((index, child_offset)) = pending.pop() {
+                match self.last_child_offset(index) {
+                    Some(last_child_offset) if child_offset <= last_child_offset => {
+                        if child_offset > 0 {
+                            newick.push(',');
+                        }
+                        pending.push((index, child_offset + 1));
+
+                        let child_index = self.child_index(index, child_offset);
+                        if self.node(child_index).is_some() {
+                            self.open_newick_node(child_index, &mut newick);
+                            pending.push((child_index, 0));
+                        }
+                    }
+                    last_child_offset => {
+                        if last_child_offset.is_some() {
+                            newick.push(')');
+                        }
+                        let value = self.nodes[index]
+                            .as_ref()
+                            .expect("a value should exist at the index");
+                        write!(newick, "{}", value).expect("writing to a string should not fail");
+                    }
+                }
+            }
+        }
+
+        newick.push(';');
+        newick
+    }
+
+    fn open_newick_node(&self, index: usize, newick: &mut String) {
+        if self.last_child_offset(index).is_some() {
+            newick.push('(');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::NewickError, test_util::sample_tree, EytzingerTree};
+
+    #[test]
+    fn to_newick() {
+        assert_eq!(sample_tree().to_newick(), "((1,(3)4)2,(,8)7)5;");
+    }
+
+    #[test]
+    fn to_newick_empty() {
+        assert_eq!(EytzingerTree::<u32>::new(2).to_newick(), ";");
+    }
+
+    #[test]
+    fn to_newick_root_only() {
+        let mut tree = EytzingerTree::<u32>::new(2);
+        tree.set_root_value(5);
+
+        assert_eq!(tree.to_newick(), "5;");
+    }
+
+    #[test]
+    fn from_newick_round_trips() {
+        let tree = sample_tree();
+
+        let parsed = EytzingerTree::<u32>::from_newick(&tree.to_newick(), 2).unwrap();
+
+        assert_eq!(parsed, tree);
+    }
+
+    #[test]
+    fn from_newick_empty() {
+        let parsed = EytzingerTree::<u32>::from_newick(";", 2).unwrap();
+
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn from_newick_too_many_children() {
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("(1,2,3)5;", 2),
+            Err(NewickError::TooManyChildren { pos: 4 })
+        );
+    }
+
+    #[test]
+    fn from_newick_unexpected_token() {
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("(1,2)5);", 2),
+            Err(NewickError::UnexpectedToken { pos: 6 })
+        );
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("(1,2)5", 2),
+            Err(NewickError::UnexpectedToken { pos: 6 })
+        );
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("(1,2)5;3", 2),
+            Err(NewickError::UnexpectedToken { pos: 7 })
+        );
+    }
+
+    #[test]
+    fn from_newick_trailing_whitespace() {
+        let parsed = EytzingerTree::<u32>::from_newick("(1)2;\n", 2).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("(1)2; 3", 2),
+            Err(NewickError::UnexpectedToken { pos: 6 })
+        );
+    }
+
+    #[test]
+    fn from_newick_invalid_arity() {
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("1;", 0),
+            Err(NewickError::InvalidArity)
+        );
+    }
+
+    #[test]
+    fn from_newick_round_trips_deep() {
+        let mut chain = EytzingerTree::<u32>::new(1);
+        let mut index = 0;
+        for value in 0..70 {
+            chain.set_value(index, value);
+            index = chain.child_index(index, 0);
+        }
+        let parsed = EytzingerTree::<u32>::from_newick(&chain.to_newick(), 1).unwrap();
+        assert_eq!(parsed, chain);
+
+        let mut right_chain = EytzingerTree::<u32>::new(2);
+        let mut index = 0;
+        for value in 0..20 {
+            right_chain.set_value(index, value);
+            index = right_chain.child_index(index, 1);
+        }
+        let parsed = EytzingerTree::<u32>::from_newick(&right_chain.to_newick(), 2).unwrap();
+        assert_eq!(parsed, right_chain);
+    }
+
+    #[test]
+    fn from_newick_too_deep() {
+        // the index of a node at depth 65 of a binary tree can not be represented by a usize
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick(
+                &format!("{}1{};", "(".repeat(70), ")".repeat(70)),
+                2
+            ),
+            Err(NewickError::TooDeep { pos: 64 })
+        );
+
+        // the index of a grandchild can be represented but not allocated
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("((1)2)3;", 1 << 62),
+            Err(NewickError::TooDeep { pos: 2 })
+        );
+    }
+
+    #[test]
+    fn from_newick_invalid_value() {
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("(1,x)5;", 2),
+            Err(NewickError::InvalidValue { pos: 4 })
+        );
+        assert_eq!(
+            EytzingerTree::<u32>::from_newick("(1,2);", 2),
+            Err(NewickError::InvalidValue { pos: 5 })
+        );
+    }
+}
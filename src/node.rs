@@ -1,7 +1,9 @@
 use crate::{
-    BreadthFirstIter, DepthFirstIter, DepthFirstOrder, EytzingerTree, NodeChildIter, NodeMut,
+    BreadthFirstIter, DepthFirstIter, DepthFirstOrder, EytzingerTree, Neighbors, NodeChildIter,
+    NodeMut, TreeStorage,
 };
-use std::ops::Deref;
+use alloc::vec;
+use core::{ops::Deref, ptr};
 
 /// Represents a borrowed node in the Eytzinger tree. This node may be used to navigate to parent or
 /// child nodes.
@@ -18,10 +20,7 @@ impl<'a, N> Copy for Node<'a, N> {}
 
 impl<'a, N> Clone for Node<'a, N> {
     fn clone(&self) -> Self {
-        Self {
-            tree: self.tree,
-            index: self.index,
-        }
+        *self
     }
 }
 
@@ -50,6 +49,97 @@ impl<'a, N> Node<'a, N> {
         self.index
     }
 
+    /// Gets whether this and `other` refer to the same node of the same tree. Unlike `==` this does
+    /// not compare trees by value so nodes at the same position of equal but distinct trees are
+    /// not the same.
+    pub fn ptr_eq(&self, other: &Node<'_, N>) -> bool {
+        ptr::eq(self.tree, other.tree) && self.index == other.index
+    }
+
+    /// Gets whether this node is on the path from the root to `other`, a node is not an ancestor of
+    /// itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is not from the same tree as this node.
+    pub fn is_ancestor_of(&self, other: &Node<'_, N>) -> bool {
+        assert!(
+            ptr::eq(self.tree, other.tree),
+            "the nodes should be from the same tree"
+        );
+
+        // parents are always stored before their children so the walk can stop once it passes
+        // this node
+        let mut current = other.index;
+        while let Some(parent_index) = self.tree.parent_index(current) {
+            if parent_index <= self.index {
+                return parent_index == self.index;
+            }
+            current = parent_index;
+        }
+
+        false
+    }
+
+    /// Gets whether the subtrees rooted at this node and `other` have the same shape and values.
+    /// Unlike `==` the nodes may be at different positions and in different trees.
+    pub fn subtree_eq(&self, other: &Node<'_, N>) -> bool
+    where
+        N: PartialEq,
+    {
+        let mut pending = vec![(*self, *other)];
+
+        while let Some((node, other_node)) = pending.pop() {
+            if node.value() != other_node.value() {
+                return false;
+            }
+
+            let mut children = node.children_indexed();
+            let mut other_children = other_node.children_indexed();
+            loop {
+                match (children.next(), other_children.next()) {
+                    (None, None) => break,
+                    (Some((index, child)), Some((other_index, other_child)))
+                        if index == other_index =>
+                    {
+                        pending.push((child, other_child))
+                    }
+                    _ => return false,
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Gets whether every node of the subtree rooted at `pattern` has a node with an equal value at
+    /// the same position in the subtree rooted at this node, the nodes must also allow the same
+    /// number of children. Nodes of this subtree which are not in `pattern` are ignored.
+    pub(crate) fn subtree_matches(&self, pattern: &Node<'_, N>) -> bool
+    where
+        N: PartialEq,
+    {
+        let mut pending = vec![(*self, *pattern)];
+
+        while let Some((node, pattern_node)) = pending.pop() {
+            if node.value() != pattern_node.value()
+                || node.tree.child_count(node.index)
+                    != pattern_node.tree.child_count(pattern_node.index)
+            {
+                return false;
+            }
+
+            for (index, pattern_child) in pattern_node.children_indexed() {
+                match node.child(index) {
+                    Some(child) => pending.push((child, pattern_child)),
+                    None => return false,
+                }
+            }
+        }
+
+        true
+    }
+
     /// Gets the value stored at this node.
     ///
     /// # Examples
@@ -69,10 +159,46 @@ impl<'a, N> Node<'a, N> {
     pub fn value(&self) -> &'a N {
         self.tree
             .value(self.index)
-            .and_then(|n| n.as_ref())
             .expect("a value should exist at the index")
     }
 
+    /// Gets a clone of the value stored at this node.
+    pub fn to_value(&self) -> N
+    where
+        N: Clone,
+    {
+        self.value().clone()
+    }
+
+    /// Gets a clone of the value stored at this node, this is the same as `to_value`.
+    pub fn cloned(&self) -> N
+    where
+        N: Clone,
+    {
+        self.to_value()
+    }
+
+    /// Gets the height of this node, this is the number of edges on the longest path from this
+    /// node to a leaf. A leaf has a height of 0.
+    pub fn height(&self) -> usize {
+        self.tree
+            .walk_heights(*self, |_, _| true)
+            .expect("the walk should not be stopped early")
+    }
+
+    /// Gets the balance factor of this node in a binary tree, this is the height of the left
+    /// subtree minus the height of the right subtree. A missing child has a height of -1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_children_per_node` is not 2.
+    pub fn balance_factor(&self) -> i64 {
+        self.tree.require_arity(2);
+
+        let child_height = |index| self.child(index).map_or(-1, |child| child.height() as i64);
+        child_height(0) - child_height(1)
+    }
+
     /// Gets the parent of this node or `None` is there was none.
     ///
     /// # Examples
@@ -148,11 +274,78 @@ impl<'a, N> Node<'a, N> {
         NodeChildIter::new(*self)
     }
 
+    /// Gets a view of the parent, children and siblings of this node.
+    pub fn neighbors(&self) -> Neighbors<'a, N> {
+        Neighbors::new(*self)
+    }
+
+    /// Gets an iterator over the immediate children of this node along with the child index each
+    /// was found at. This only includes children for which there is a node.
+    pub fn children_indexed(&self) -> impl Iterator<Item = (usize, Node<'a, N>)> {
+        let node = *self;
+        (0..self.tree.child_count(self.index)).filter_map(move |child_index| {
+            node.child(child_index).map(|child| (child_index, child))
+        })
+    }
+
+    /// Gets the lowest child index of this node for which there is no node or `None` if every
+    /// child index is occupied.
+    pub fn has_vacant_child(&self) -> Option<usize> {
+        (0..self.tree.child_count(self.index))
+            .find(|&child_index| self.child(child_index).is_none())
+    }
+
+    /// Gets the lowest child index of this node for which there is a node whose value matches the
+    /// predicate or `None` if there was no such child.
+    pub fn first_child_matching<F>(&self, mut f: F) -> Option<usize>
+    where
+        F: FnMut(&N) -> bool,
+    {
+        self.children_indexed()
+            .find(|(_, child)| f(child.value()))
+            .map(|(child_index, _)| child_index)
+    }
+
+    /// Descends from this node to a leaf by repeatedly moving to the child whose value has the
+    /// highest score. When multiple children have the highest score the child with the lowest
+    /// index is chosen.
+    ///
+    /// # Returns
+    ///
+    /// The leaf which was reached, this node if it is a leaf.
+    pub fn descend_by<F>(&self, mut f: F) -> Node<'a, N>
+    where
+        F: FnMut(&N) -> i64,
+    {
+        let mut current = *self;
+
+        loop {
+            let mut best: Option<(i64, Node<'a, N>)> = None;
+            for child in current.child_iter() {
+                let score = f(child.value());
+                match best {
+                    Some((best_score, _)) if score <= best_score => {}
+                    _ => best = Some((score, child)),
+                }
+            }
+
+            match best {
+                Some((_, child)) => current = child,
+                None => return current,
+            }
+        }
+    }
+
     /// Gets a depth-first iterator over this and all child nodes.
     pub fn depth_first_iter(&self, order: DepthFirstOrder) -> DepthFirstIter<'a, N> {
         DepthFirstIter::new(self.tree(), Some(*self), order)
     }
 
+    /// Gets an iterator over all descendants of this node in pre-order, this node is not included.
+    pub fn descendants(&self) -> impl Iterator<Item = Node<'a, N>> {
+        self.depth_first_iter(DepthFirstOrder::PreOrder).skip(1)
+    }
+
     /// Gets a breadth-first iterator over this and all child nodes.
     pub fn breadth_first_iter(&self) -> BreadthFirstIter<'a, N> {
         BreadthFirstIter::new(self.tree(), Some(*self))
@@ -187,7 +380,170 @@ impl<'a, N> From<&'a NodeMut<'a, N>> for Node<'a, N> {
 
 #[cfg(test)]
 mod test {
-    use crate::EytzingerTree;
+    use crate::{test_util::sample_tree, EytzingerTree};
+
+    #[test]
+    fn descendants() {
+        let tree = sample_tree();
+        let left = tree.root().unwrap().child(0).unwrap();
+
+        let descendants: Vec<_> = left.descendants().map(|n| *n.value()).collect();
+        assert_eq!(descendants, vec![1, 4, 3]);
+
+        let leaf = left.child(0).unwrap();
+        assert_eq!(leaf.descendants().count(), 0);
+    }
+
+    #[test]
+    fn children_indexed() {
+        let tree = sample_tree();
+
+        let children: Vec<_> = tree
+            .root()
+            .unwrap()
+            .children_indexed()
+            .map(|(index, n)| (index, *n.value()))
+            .collect();
+        assert_eq!(children, vec![(0, 2), (1, 7)]);
+
+        let right = tree.root().unwrap().child(1).unwrap();
+        let children: Vec<_> = right
+            .children_indexed()
+            .map(|(index, n)| (index, *n.value()))
+            .collect();
+        assert_eq!(children, vec![(1, 8)]);
+    }
+
+    #[test]
+    fn has_vacant_child() {
+        let tree = sample_tree();
+        let left = tree.root().unwrap().child(0).unwrap();
+
+        assert_eq!(left.has_vacant_child(), None);
+        assert_eq!(left.child(0).unwrap().has_vacant_child(), Some(0));
+        assert_eq!(
+            tree.root().unwrap().child(1).unwrap().has_vacant_child(),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn first_child_matching() {
+        let tree = sample_tree();
+        let root = tree.root().unwrap();
+
+        assert_eq!(root.first_child_matching(|value| *value > 1), Some(0));
+        assert_eq!(root.first_child_matching(|value| *value > 5), Some(1));
+        assert_eq!(root.first_child_matching(|value| *value > 7), None);
+        assert_eq!(
+            root.child(1).unwrap().first_child_matching(|_| true),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn descend_by() {
+        let tree = sample_tree();
+        let root = tree.root().unwrap();
+
+        assert_eq!(root.descend_by(|&value| i64::from(value)).value(), &8);
+        assert_eq!(root.descend_by(|&value| -i64::from(value)).value(), &1);
+        assert_eq!(root.descend_by(|_| 0).value(), &1);
+
+        let leaf = root.child(1).unwrap().child(1).unwrap();
+        assert!(leaf.descend_by(|_| 0).ptr_eq(&leaf));
+    }
+
+    #[test]
+    fn is_ancestor_of() {
+        let tree = sample_tree();
+        let root = tree.root().unwrap();
+        let left = root.child(0).unwrap();
+
+        for node in tree.breadth_first_iter().skip(1) {
+            assert!(root.is_ancestor_of(&node));
+            assert!(!node.is_ancestor_of(&root));
+        }
+        assert!(!root.is_ancestor_of(&root));
+
+        assert!(left.is_ancestor_of(&tree.node_at_path(&[0, 1, 0]).unwrap()));
+        assert!(!left.is_ancestor_of(&tree.node_at_path(&[1, 1]).unwrap()));
+
+        let leaf = tree.node_at_path(&[0, 0]).unwrap();
+        assert!(tree
+            .breadth_first_iter()
+            .all(|node| !leaf.is_ancestor_of(&node)));
+    }
+
+    #[test]
+    #[should_panic(expected = "the nodes should be from the same tree")]
+    fn is_ancestor_of_other_tree_panics() {
+        let tree = sample_tree();
+        let other = sample_tree();
+
+        tree.root()
+            .unwrap()
+            .is_ancestor_of(&other.node_at_path(&[0]).unwrap());
+    }
+
+    #[test]
+    fn subtree_eq() {
+        let tree = sample_tree();
+        let mut other_tree = EytzingerTree::new(2);
+        {
+            let mut root = other_tree.set_root_value(2);
+            root.set_child_value(0, 1);
+            root.set_child_value(1, 4).set_child_value(0, 3);
+        }
+
+        let left = tree.root().unwrap().child(0).unwrap();
+        assert!(left.subtree_eq(&other_tree.root().unwrap()));
+        assert!(!left.subtree_eq(&tree.root().unwrap()));
+
+        *other_tree
+            .root_mut()
+            .unwrap()
+            .child_mut(1)
+            .unwrap()
+            .value_mut() = 6;
+        assert!(!left.subtree_eq(&other_tree.root().unwrap()));
+    }
+
+    #[test]
+    fn balance_factor() {
+        let tree = sample_tree();
+        let root = tree.root().unwrap();
+
+        assert_eq!(root.balance_factor(), 1);
+        assert_eq!(root.child(0).unwrap().balance_factor(), -1);
+        assert_eq!(root.child(1).unwrap().balance_factor(), -1);
+
+        let mut tree = EytzingerTree::new(2);
+        tree.set_root_value(2).set_child_value(0, 1);
+        assert_eq!(tree.root().unwrap().balance_factor(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "operation requires max_children_per_node == 2, got 3")]
+    fn balance_factor_requires_binary_tree() {
+        let mut tree = EytzingerTree::new(3);
+        tree.set_root_value(1);
+
+        tree.root().unwrap().balance_factor();
+    }
+
+    #[test]
+    fn ptr_eq() {
+        let tree = sample_tree();
+        let other_tree = sample_tree();
+
+        let child = tree.root().unwrap().child(0).unwrap();
+
+        assert!(child.ptr_eq(&tree.root().unwrap().child(0).unwrap()));
+        assert!(!child.ptr_eq(&tree.root().unwrap()));
+        assert!(!child.ptr_eq(&other_tree.root().unwrap().child(0).unwrap()));
+        assert_eq!(child, other_tree.root().unwrap().child(0).unwrap());
+    }
 
     #[test]
     fn breadth_first_iter_returns_breadth_first() {
@@ -1,8 +1,13 @@
 use crate::{
     entry::{Entry, VacantEntry},
+    error::{CapacityError, ChildIndexError, NodeFull},
     BreadthFirstIter, DepthFirstIter, DepthFirstOrder, EytzingerTree, Node, NodeChildIter,
+    TreeStorage,
+};
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
 };
-use std::ops::{Deref, DerefMut};
 
 /// Represents a borrowed node in the Eytzinger tree. This node may be used mutate this node's value
 /// and child nodes.
@@ -61,10 +66,22 @@ impl<'a, N> NodeMut<'a, N> {
     pub fn value_mut(&mut self) -> &mut N {
         self.tree
             .value_mut(self.index)
-            .and_then(|v| v.as_mut())
             .expect("a value should exist at the index")
     }
 
+    /// Replaces the value stored at this node, the children of this node are left as they are.
+    ///
+    /// A vacant node may not have children so a node can not be cleared while keeping its
+    /// children. Instead a sentinel, or tombstone, value may be stored with this method to mark
+    /// the node as logically removed without changing the structure of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The old value.
+    pub fn replace_value(&mut self, value: N) -> N {
+        mem::replace(self.value_mut(), value)
+    }
+
     /// Gets the mutable value stored at this node.
     ///
     /// This differs from `value_mut` in that it takes ownership of the current node and the value
@@ -72,15 +89,49 @@ impl<'a, N> NodeMut<'a, N> {
     pub fn into_value_mut(self) -> &'a mut N {
         self.tree
             .value_mut(self.index)
-            .and_then(|v| v.as_mut())
             .expect("a value should exist at the index")
     }
 
     /// Gets the parent of this node or `None` is there was none.
-    pub fn parent(&self) -> Option<Node<N>> {
+    pub fn parent(&self) -> Option<Node<'_, N>> {
         self.as_node().parent()
     }
 
+    /// Gets the mutable value of the parent of this node or `None` if there wasn't one.
+    ///
+    /// The value is borrowed through this node so this node may not be used while the value is
+    /// borrowed, this node remains usable once the borrow ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lz_eytzinger_tree::EytzingerTree;
+    ///
+    /// let mut tree = EytzingerTree::<u32>::new(2);
+    /// {
+    ///     let mut root = tree.set_root_value(5);
+    ///     let mut child = root.set_child_value(0, 2);
+    ///     *child.parent_value_mut().unwrap() += 1;
+    ///     assert_eq!(child.value(), &2);
+    /// }
+    ///
+    /// assert_eq!(tree.root().unwrap().value(), &6);
+    /// ```
+    pub fn parent_value_mut(&mut self) -> Option<&mut N> {
+        let parent_index = self.tree.parent_index(self.index)?;
+        self.tree.value_mut(parent_index)
+    }
+
+    /// Gets the mutable value of the child of this node at the specified index or `None` if there
+    /// wasn't one.
+    ///
+    /// The value is borrowed through this node so this node may not be used while the value is
+    /// borrowed, this node remains usable once the borrow ends.
+    pub fn child_value_mut(&mut self, index: usize) -> Option<&mut N> {
+        let child_index = self.tree.child_index(self.index, index);
+        self.tree.value_mut(child_index)
+    }
+
     /// Gets the mutable paret of this node or `None` if there wasn't one.
     ///
     /// This differs from `parent_mut` in that it takes ownership of the current node and is
@@ -96,13 +147,24 @@ impl<'a, N> NodeMut<'a, N> {
         }
     }
 
+    /// Gets the mutable root of the tree this node is in. As this node exists the root must also
+    /// exist.
+    ///
+    /// This takes ownership of the current node and the root is lifetime bound to the tree.
+    pub fn to_root(self) -> Self {
+        self.tree
+            .node_mut(0)
+            .ok()
+            .expect("the root should exist when any node exists")
+    }
+
     /// Gets the child of this node at the specified index or `None` if there wasn't one.
-    pub fn child(&self, index: usize) -> Option<Node<N>> {
+    pub fn child(&self, index: usize) -> Option<Node<'_, N>> {
         self.as_node().child(index)
     }
 
     /// Gets the mutable child of this node at the specified index or `None` if there wasn't one.
-    pub fn child_mut(&mut self, index: usize) -> Option<NodeMut<N>> {
+    pub fn child_mut(&mut self, index: usize) -> Option<NodeMut<'_, N>> {
         self.tree.child_mut(self.index, index).ok()
     }
 
@@ -123,26 +185,166 @@ impl<'a, N> NodeMut<'a, N> {
 
     /// Sets the value of the child at the specified index.
     ///
+    /// # Panics
+    ///
+    /// Panics if the index of the child exceeds the capacity of the tree, `try_set_child_value`
+    /// may be used to handle this instead.
+    ///
     /// # Returns
     ///
     /// The new mutable child.
-    pub fn set_child_value(&mut self, index: usize, new_value: N) -> NodeMut<N> {
+    pub fn set_child_value(&mut self, index: usize, new_value: N) -> NodeMut<'_, N> {
         self.tree.set_child_value(self.index, index, new_value)
     }
 
+    /// Sets the value of the child at the specified index, failing rather than panicking if the
+    /// child can not be stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CapacityError` if the index of the child can not be represented by a `usize` or
+    /// the storage for the child can not be allocated.
+    ///
+    /// # Returns
+    ///
+    /// The new mutable child.
+    pub fn try_set_child_value(
+        &mut self,
+        index: usize,
+        new_value: N,
+    ) -> Result<NodeMut<'_, N>, CapacityError> {
+        self.tree.try_set_child_value(self.index, index, new_value)
+    }
+
+    /// Sets the value of the child at the specified index, failing rather than panicking if the
+    /// index is out of range. This may be used to validate child indexes supplied by users.
+    ///
+    /// This is not named `try_set_child_value` as that already handles capacity failures. The
+    /// value must be present rather than any `Into<Option<N>>` as there would be no child to
+    /// return for `None`, `remove_child_value` may be used to clear a child instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index of the child exceeds the capacity of the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChildIndexError` if `index` is not less than the number of children this node may
+    /// have, this is `max_children_per_node` unless the tree was created with `mixed_arity`.
+    ///
+    /// # Returns
+    ///
+    /// The new mutable child.
+    pub fn checked_set_child_value(
+        &mut self,
+        index: usize,
+        new_value: N,
+    ) -> Result<NodeMut<'_, N>, ChildIndexError> {
+        let max = self.tree.child_count(self.index);
+        if index >= max {
+            return Err(ChildIndexError { child: index, max });
+        }
+
+        Ok(self.set_child_value(index, new_value))
+    }
+
+    /// Sets the value of the lowest vacant child of this node.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NodeFull` if every child of this node is occupied.
+    ///
+    /// # Returns
+    ///
+    /// The new mutable child.
+    pub fn append_child(&mut self, value: N) -> Result<NodeMut<'_, N>, NodeFull> {
+        let index = self.as_node().has_vacant_child().ok_or(NodeFull)?;
+
+        Ok(self.set_child_value(index, value))
+    }
+
+    /// Inserts a value for the child at the specified index.
+    ///
+    /// Unlike `set_child_value` this returns the previous value rather than the child. When the
+    /// child was occupied only its value is replaced, the existing children of the child remain
+    /// attached to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `max_children_per_node`.
+    ///
+    /// # Returns
+    ///
+    /// The previous value of the child if there was one.
+    pub fn insert_child(&mut self, index: usize, value: N) -> Option<N> {
+        match self.child_entry(index) {
+            Entry::Occupied(mut child) => Some(child.replace_value(value)),
+            Entry::Vacant(child) => {
+                child.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Sets the values of the children of this node in order starting from the child at index 0.
+    /// A `Some` value replaces the value of the child, leaving any existing children of the child
+    /// attached to it, while `None` removes the child along with all of its descendants. Children
+    /// after the last value are left as they are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` yields more values than this node may have children.
+    pub fn set_children<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = Option<N>>,
+    {
+        let child_count = self.tree.child_count(self.index);
+
+        for (child_offset, value) in values.into_iter().enumerate() {
+            assert!(
+                child_offset < child_count,
+                "the number of children should not exceed {}",
+                child_count
+            );
+
+            match value {
+                Some(value) => {
+                    self.set_child_value(child_offset, value);
+                }
+                None => {
+                    self.remove_child_value(child_offset);
+                }
+            }
+        }
+    }
+
+    /// Gets the mutable child of this node at the specified index, creating it with `default` if
+    /// there wasn't one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `max_children_per_node`.
+    ///
+    /// # Returns
+    ///
+    /// The mutable child, this may be new or may have already existed.
+    pub fn get_or_create_child(&mut self, index: usize, default: N) -> NodeMut<'_, N> {
+        self.child_entry(index).or_insert(default)
+    }
+
     /// Removes the child value at the specified child index. This will also remove all children of
     /// the specified child.
     ///
     /// # Returns
     ///
     /// The old child value if there was one.
-    pub fn remove_child_value(&mut self, index: usize) -> (Option<N>, VacantEntry<N>) {
+    pub fn remove_child_value(&mut self, index: usize) -> (Option<N>, VacantEntry<'_, N>) {
         self.child_entry(index).remove()
     }
 
     /// Gets the child entry of this node at the specified index. This node is not consumed in the
     /// process so the child entry is lifetime bound to this node.
-    pub fn child_entry(&mut self, index: usize) -> Entry<N> {
+    pub fn child_entry(&mut self, index: usize) -> Entry<'_, N> {
         self.tree.child_entry(self.index, index)
     }
 
@@ -154,6 +356,18 @@ impl<'a, N> NodeMut<'a, N> {
         self.tree.child_entry(self.index, index)
     }
 
+    /// Retains only the descendants of this node for which `f` returns `true`, this node and any
+    /// nodes outside of its subtree are left as they are.
+    ///
+    /// Descendants are visited in pre-order, when a descendant is not retained its whole subtree is
+    /// removed without being visited.
+    pub fn retain_descendants<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&N) -> bool,
+    {
+        self.tree.retain_descendants(self.index, |value| f(value))
+    }
+
     /// Removes this node from the tree.
     ///
     /// # Examples
@@ -186,9 +400,15 @@ impl<'a, N> NodeMut<'a, N> {
         (value, entry)
     }
 
+    /// Removes this node and all of its descendants from the tree, returning only the value of
+    /// this node.
+    pub fn into_value(self) -> N {
+        self.remove().0
+    }
+
     /// Gets a view of this mutable node as an immutable node. The resulting node is lifetime bound
     /// to this node so the immutable node may not outlive this mutable node.
-    pub fn as_node(&self) -> Node<N> {
+    pub fn as_node(&self) -> Node<'_, N> {
         Node {
             tree: self.tree,
             index: self.index,
@@ -218,22 +438,34 @@ impl<'a, N> NodeMut<'a, N> {
     /// let child_values: Vec<_> = root.child_iter().map(|n| n.value()).collect();
     /// assert_eq!(child_values, vec![&1, &3]);
     /// ```
-    pub fn child_iter(&self) -> NodeChildIter<N> {
+    pub fn child_iter(&self) -> NodeChildIter<'_, N> {
         self.as_node().child_iter()
     }
 
+    /// Gets the lowest child index of this node for which there is a node whose value matches the
+    /// predicate or `None` if there was no such child.
+    ///
+    /// This may be used to find the index to pass to `to_child` when the index is not known up
+    /// front, such as when searching the tree.
+    pub fn first_child_matching<F>(&self, f: F) -> Option<usize>
+    where
+        F: FnMut(&N) -> bool,
+    {
+        self.as_node().first_child_matching(f)
+    }
+
     /// Gets a depth-first iterator over this and all child nodes.
-    pub fn depth_first_iter(&self, order: DepthFirstOrder) -> DepthFirstIter<N> {
+    pub fn depth_first_iter(&self, order: DepthFirstOrder) -> DepthFirstIter<'_, N> {
         self.as_node().depth_first_iter(order)
     }
 
     /// Gets a breadth-first iterator over this and all child nodes.
-    pub fn breadth_first_iter(&self) -> BreadthFirstIter<N> {
+    pub fn breadth_first_iter(&self) -> BreadthFirstIter<'_, N> {
         self.as_node().breadth_first_iter()
     }
 
     pub fn split_off(self) -> EytzingerTree<N> {
-        self.tree.split_off(self.index)
+        self.tree.split_off_index(self.index)
     }
 }
 
@@ -253,7 +485,258 @@ impl<'a, N> DerefMut for NodeMut<'a, N> {
 
 #[cfg(test)]
 mod tests {
-    use crate::EytzingerTree;
+    use crate::{
+        error::{CapacityError, ChildIndexError, NodeFull},
+        test_util::sample_tree,
+        EytzingerTree,
+    };
+
+    #[test]
+    fn first_child_matching_descends_to_smallest_child() {
+        let mut tree = sample_tree();
+        let mut node = tree.root_mut().unwrap();
+
+        while let Some(smallest) = node.child_iter().map(|child| *child.value()).min() {
+            let index = node
+                .first_child_matching(|value| *value == smallest)
+                .unwrap();
+            node = node.to_child(index).ok().unwrap();
+            *node.value_mut() *= 10;
+        }
+
+        assert_eq!(*node.value(), 10);
+        assert_eq!(
+            tree.breadth_first_iter()
+                .map(|n| *n.value())
+                .collect::<Vec<_>>(),
+            vec![5, 20, 7, 10, 4, 8, 3]
+        );
+    }
+
+    #[test]
+    fn insert_child_replaces_value() {
+        let mut tree = sample_tree();
+        let mut root = tree.root_mut().unwrap();
+
+        assert_eq!(root.insert_child(0, 6), Some(2));
+        assert_eq!(root.child(0).unwrap().child(1).map(|n| *n.value()), Some(4));
+
+        let mut right = root.to_child(1).ok().unwrap();
+        assert_eq!(right.insert_child(1, 9), Some(8));
+        assert_eq!(right.insert_child(0, 6), None);
+
+        assert_eq!(tree.len(), 8);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn checked_set_child_value() {
+        let mut tree = EytzingerTree::new(2);
+        let mut root = tree.set_root_value(1);
+
+        assert_eq!(
+            root.checked_set_child_value(2, 3).map(|n| *n.value()),
+            Err(ChildIndexError { child: 2, max: 2 })
+        );
+        assert_eq!(
+            root.checked_set_child_value(1, 3).map(|n| *n.value()),
+            Ok(3)
+        );
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn append_child() {
+        let mut tree = EytzingerTree::new(2);
+        let mut root = tree.set_root_value(1);
+
+        assert_eq!(root.append_child(2).map(|n| n.index), Ok(1));
+        assert_eq!(root.append_child(3).map(|n| n.index), Ok(2));
+        assert_eq!(root.append_child(4).map(|n| n.index), Err(NodeFull));
+
+        let values: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn try_set_child_value_exceeding_capacity() {
+        let arity = usize::MAX / 2 + 1;
+        let mut tree = EytzingerTree::new(arity);
+        let mut root = tree.set_root_value(1);
+        let mut child = root.try_set_child_value(0, 2).unwrap();
+
+        // the index can be represented but the storage for it can not be allocated
+        assert_eq!(
+            child.try_set_child_value(0, 3).map(|n| *n.value()),
+            Err(CapacityError)
+        );
+        // the index can not be represented
+        assert_eq!(
+            child.try_set_child_value(arity - 1, 3).map(|n| *n.value()),
+            Err(CapacityError)
+        );
+
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn to_root_from_leaf() {
+        let mut tree = sample_tree();
+
+        let leaf = tree
+            .root_mut()
+            .unwrap()
+            .to_child(0)
+            .ok()
+            .unwrap()
+            .to_child(1)
+            .ok()
+            .unwrap()
+            .to_child(0)
+            .ok()
+            .unwrap();
+        assert_eq!(leaf.value(), &3);
+
+        let root = leaf.to_root();
+        assert_eq!(root.value(), &5);
+    }
+
+    #[test]
+    fn replace_value_keeps_children() {
+        let mut tree = sample_tree().map(Some);
+        {
+            let mut left = tree.root_mut().unwrap().to_child(0).ok().unwrap();
+            assert_eq!(left.replace_value(None), Some(2));
+        }
+
+        assert_eq!(tree.len(), 7);
+
+        let breadth_first: Vec<_> = tree
+            .breadth_first_iter()
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(
+            breadth_first,
+            vec![Some(5), None, Some(7), Some(1), Some(4), Some(8), Some(3)]
+        );
+    }
+
+    #[test]
+    fn retain_descendants() {
+        let mut tree = sample_tree();
+        tree.root_mut()
+            .unwrap()
+            .to_child(0)
+            .ok()
+            .unwrap()
+            .retain_descendants(|&value| value % 2 == 1);
+
+        assert_eq!(tree.len(), 5);
+
+        let breadth_first: Vec<_> = tree
+            .breadth_first_iter()
+            .map(|n| n.value())
+            .cloned()
+            .collect();
+
+        assert_eq!(breadth_first, vec![5, 2, 7, 1, 8]);
+    }
+
+    #[test]
+    fn parent_value_mut() {
+        let mut tree = sample_tree();
+        {
+            let mut child = tree.root_mut().unwrap().to_child(1).ok().unwrap();
+            *child.parent_value_mut().unwrap() += 1;
+            assert_eq!(child.value(), &7);
+        }
+
+        assert_eq!(tree.root().unwrap().value(), &6);
+        assert_eq!(tree.root_mut().unwrap().parent_value_mut(), None);
+    }
+
+    #[test]
+    fn child_value_mut() {
+        let mut tree = sample_tree();
+        {
+            let mut root = tree.root_mut().unwrap();
+            *root.child_value_mut(0).unwrap() += 1;
+            assert_eq!(root.value(), &5);
+
+            let mut right = root.to_child(1).ok().unwrap();
+            assert_eq!(right.child_value_mut(0), None);
+        }
+
+        assert_eq!(tree.root().unwrap().child(0).unwrap().value(), &3);
+    }
+
+    #[test]
+    fn get_or_create_child_only_inserts_once() {
+        let mut tree = EytzingerTree::new(2);
+        let mut root = tree.set_root_value(5);
+
+        assert_eq!(root.get_or_create_child(0, 2).value(), &2);
+        assert_eq!(root.get_or_create_child(0, 3).value(), &2);
+
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_or_create_child_panics_for_out_of_range_index() {
+        let mut tree = EytzingerTree::new(2);
+        let mut root = tree.set_root_value(5);
+
+        root.get_or_create_child(2, 2);
+    }
+
+    #[test]
+    fn into_value() {
+        let mut tree = sample_tree();
+        let len = tree.len();
+
+        let leaf = tree
+            .root_mut()
+            .unwrap()
+            .to_child(1)
+            .ok()
+            .unwrap()
+            .to_child(1)
+            .ok()
+            .unwrap();
+
+        assert_eq!(leaf.into_value(), 8);
+        assert_eq!(tree.len(), len - 1);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn set_children() {
+        let mut tree = EytzingerTree::new(2);
+        tree.set_root_value(5).set_children(vec![Some(2), Some(7)]);
+
+        assert_eq!(tree.len(), 3);
+        let breadth_first: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(breadth_first, vec![5, 2, 7]);
+
+        let mut tree = sample_tree();
+        tree.root_mut().unwrap().set_children(vec![None, Some(9)]);
+
+        assert_eq!(tree.len(), 3);
+        let breadth_first: Vec<_> = tree.breadth_first_iter().map(|n| *n.value()).collect();
+        assert_eq!(breadth_first, vec![5, 9, 8]);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of children should not exceed 2")]
+    fn set_children_exceeding_max_children_panics() {
+        let mut tree = EytzingerTree::new(2);
+        tree.set_root_value(5)
+            .set_children(vec![Some(1), Some(2), Some(3)]);
+    }
 
     #[test]
     fn split_off() {
@@ -291,5 +774,4 @@ mod tests {
         assert_eq!(tree, expected_remaining);
         assert_eq!(split_off, expected_split_off);
     }
-
 }
@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+const BITS: usize = 64;
+
+/// A bitset recording which slots of an Eytzinger tree's storage are occupied. This allows runs of
+/// vacant slots to be skipped a word at a time and occupied slots to be counted without visiting
+/// each slot.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub(crate) struct Occupancy {
+    words: Vec<u64>,
+}
+
+impl Occupancy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.words
+            .get(index / BITS)
+            .is_some_and(|word| word & (1 << (index % BITS)) != 0)
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        let word_index = index / BITS;
+        if word_index >= self.words.len() {
+            self.words.resize(word_index + 1, 0);
+        }
+
+        self.words[word_index] |= 1 << (index % BITS);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if let Some(word) = self.words.get_mut(index / BITS) {
+            *word &= !(1 << (index % BITS));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// Removes all indexes greater than or equal to `len`.
+    pub fn truncate(&mut self, len: usize) {
+        self.words.truncate(len.div_ceil(BITS));
+
+        if len % BITS != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (len % BITS)) - 1;
+            }
+        }
+    }
+
+    /// Gets the number of occupied indexes within the range.
+    pub fn count(&self, range: Range<usize>) -> usize {
+        self.iter_range(range).count()
+    }
+
+    /// Gets the highest occupied index.
+    pub fn last(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .rposition(|&word| word != 0)
+            .map(|word_index| {
+                word_index * BITS + (BITS - 1 - self.words[word_index].leading_zeros() as usize)
+            })
+    }
+
+    /// Gets an iterator over the occupied indexes in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.iter_range(0..usize::MAX)
+    }
+
+    /// Gets an iterator over the occupied indexes within the range in ascending order.
+    pub fn iter_range(&self, range: Range<usize>) -> impl Iterator<Item = usize> + '_ {
+        let end = range.end;
+        let mut word_index = range.start / BITS;
+        let mut word = self
+            .words
+            .get(word_index)
+            .map_or(0, |word| word & (!0 << (range.start % BITS)));
+
+        core::iter::from_fn(move || {
+            while word == 0 {
+                word_index += 1;
+                word = *self.words.get(word_index)?;
+            }
+
+            let bit = word.trailing_zeros() as usize;
+            word &= word - 1;
+
+            Some(word_index * BITS + bit)
+        })
+        .take_while(move |&index| index < end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Occupancy;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut occupancy = Occupancy::new();
+        occupancy.insert(3);
+        occupancy.insert(64);
+        occupancy.insert(200);
+
+        assert!(occupancy.contains(3));
+        assert!(!occupancy.contains(4));
+        assert!(occupancy.contains(200));
+        assert_eq!(occupancy.iter().collect::<Vec<_>>(), vec![3, 64, 200]);
+        assert_eq!(occupancy.last(), Some(200));
+
+        occupancy.remove(200);
+        assert_eq!(occupancy.last(), Some(64));
+        assert_eq!(occupancy.iter().collect::<Vec<_>>(), vec![3, 64]);
+    }
+
+    #[test]
+    fn count_range() {
+        let mut occupancy = Occupancy::new();
+        for index in (0..300).step_by(3) {
+            occupancy.insert(index);
+        }
+
+        assert_eq!(occupancy.count(0..300), 100);
+        assert_eq!(occupancy.count(1..3), 0);
+        assert_eq!(occupancy.count(63..130), 23);
+        assert_eq!(occupancy.count(297..usize::MAX), 1);
+        assert_eq!(occupancy.count(1000..2000), 0);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut occupancy = Occupancy::new();
+        occupancy.insert(0);
+        occupancy.insert(65);
+        occupancy.insert(66);
+
+        occupancy.truncate(66);
+
+        assert_eq!(occupancy.iter().collect::<Vec<_>>(), vec![0, 65]);
+    }
+}
@@ -0,0 +1,18 @@
+/// How an operation which places nodes at a destination treats a destination which is already
+/// occupied.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OverwritePolicy {
+    /// The operation fails if there is already a node at the destination, the tree is left
+    /// unchanged.
+    Error,
+
+    /// Any node at the destination is removed along with its subtree before the new nodes are
+    /// placed.
+    Overwrite,
+
+    /// The node at the destination and its subtree are kept, the value of the source root is not
+    /// used and the descendants of the source are placed in the vacant slots at the same positions
+    /// below the destination. The operation fails, leaving the tree unchanged, if any of those
+    /// slots is occupied. When the destination is vacant this is the same as `Error`.
+    Merge,
+}
@@ -0,0 +1,29 @@
+use crate::EytzingerTree;
+use rayon::prelude::*;
+
+impl<N> EytzingerTree<N>
+where
+    N: Sync,
+{
+    /// Gets a parallel iterator over the values of all nodes. The values are not returned in any
+    /// particular order.
+    pub fn par_values(&self) -> impl ParallelIterator<Item = &N> {
+        self.nodes.par_iter().filter_map(Option::as_ref)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::EytzingerTree;
+    use rayon::prelude::*;
+
+    #[test]
+    fn par_values_sum() {
+        let tree = EytzingerTree::perfect_from_fn(3, 8, |path| path.iter().sum::<usize>() as u64);
+
+        let sequential: u64 = tree.breadth_first_iter().map(|n| *n.value()).sum();
+        let parallel: u64 = tree.par_values().sum();
+
+        assert_eq!(parallel, sequential);
+    }
+}
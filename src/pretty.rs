@@ -0,0 +1,102 @@
+use crate::EytzingerTree;
+use alloc::{string::String, vec, vec::Vec};
+
+impl<N> EytzingerTree<N> {
+    /// Formats the tree as an indented ASCII tree with a line per node, `f` renders each node from
+    /// its value and path of child offsets from the root. Children are listed below their parent
+    /// in order of their child index, vacant child slots are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lz_eytzinger_tree::EytzingerTree;
+    ///
+    /// let mut tree = EytzingerTree::<u32>::new(2);
+    /// {
+    ///     let mut root = tree.set_root_value(5);
+    ///     root.set_child_value(0, 2).set_child_value(1, 4);
+    ///     root.set_child_value(1, 7);
+    /// }
+    ///
+    /// let formatted = tree.format_tree(|value, path| format!("{} {:?}", value, path));
+    /// assert_eq!(
+    ///     formatted,
+    ///     "5 []\n|-- 2 [0]\n|   `-- 4 [0, 1]\n`-- 7 [1]\n"
+    /// );
+    /// ```
+    pub fn format_tree<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&N, &[usize]) -> String,
+    {
+        let mut formatted = String::new();
+
+        let root = match self.root() {
+            Some(root) => root,
+            None => return formatted,
+        };
+
+        // each pending node has the prefix of its own line and the prefix of its children's lines
+        let mut pending = vec![(root, String::new(), String::new())];
+        while let Some((node, line_prefix, child_prefix)) = pending.pop() {
+            let path = self.index_calculator.path(node.index);
+
+            formatted.push_str(&line_prefix);
+            formatted.push_str(&f(node.value(), &path));
+            formatted.push('\n');
+
+            // children are pushed in reverse so they are popped in order
+            let children: Vec<_> = node.child_iter().collect();
+            let last_position = children.len().saturating_sub(1);
+            for (position, child) in children.into_iter().enumerate().rev() {
+                let (line_marker, child_marker) = if position == last_position {
+                    ("`-- ", "    ")
+                } else {
+                    ("|-- ", "|   ")
+                };
+
+                pending.push((
+                    child,
+                    child_prefix.clone() + line_marker,
+                    child_prefix.clone() + child_marker,
+                ));
+            }
+        }
+
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::sample_tree, EytzingerTree};
+
+    #[test]
+    fn format_tree() {
+        let formatted =
+            sample_tree().format_tree(|value, path| format!("{}@{}", value, path.len()));
+
+        let expected = [
+            "5@0",
+            "|-- 2@1",
+            "|   |-- 1@2",
+            "|   `-- 4@2",
+            "|       `-- 3@3",
+            "`-- 7@1",
+            "    `-- 8@2",
+        ];
+        assert_eq!(
+            formatted,
+            expected
+                .iter()
+                .map(|line| format!("{}\n", line))
+                .collect::<String>()
+        );
+    }
+
+    #[test]
+    fn format_tree_empty() {
+        let tree = EytzingerTree::<u32>::new(2);
+
+        assert_eq!(tree.format_tree(|value, _| value.to_string()), "");
+    }
+}
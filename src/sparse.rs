@@ -0,0 +1,325 @@
+use crate::{
+    error::PathError,
+    traversal::{BreadthFirstIterator, DepthFirstIterator, DepthFirstOrder},
+    tree_storage::sealed,
+    EytzingerIndexCalculator, EytzingerTree, StorageNode, TreeStorage,
+};
+use core::ops::Range;
+use std::collections::HashMap;
+
+/// An N-tree using the same Eytzinger layout as an `EytzingerTree` but storing only occupied
+/// positions, keyed by their index.
+///
+/// This uses memory proportional to the number of nodes rather than the deepest, right-most
+/// position, so it suits trees where `EytzingerTree::density` is low, such as deep chains. A node
+/// and its children must still have indexes addressable by a `usize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseEytzingerTree<N> {
+    nodes: HashMap<usize, N>,
+    index_calculator: EytzingerIndexCalculator,
+}
+
+impl<N> SparseEytzingerTree<N> {
+    /// Creates a new, empty, sparse Eytzinger tree with the specified maximum children per node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_children_per_node` is 0.
+    pub fn new(max_children_per_node: usize) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            index_calculator: EytzingerIndexCalculator::new(max_children_per_node),
+        }
+    }
+
+    /// Gets the maximum number of children per node.
+    pub fn max_children_per_node(&self) -> usize {
+        self.index_calculator.max_children_per_node()
+    }
+
+    /// Gets the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Gets whether the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Removes all nodes from the tree.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Gets the value of the node at the specified path of child offsets from the root, `None`
+    /// if there is no such node.
+    pub fn get(&self, path: &[usize]) -> Option<&N> {
+        self.nodes.get(&self.index_calculator.path_index(path)?)
+    }
+
+    /// Gets the mutable value of the node at the specified path of child offsets from the root,
+    /// `None` if there is no such node.
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut N> {
+        let index = self.index_calculator.path_index(path)?;
+        self.nodes.get_mut(&index)
+    }
+
+    /// Gets the root node of the tree, `None` if the tree is empty.
+    pub fn root(&self) -> Option<StorageNode<'_, N, Self>> {
+        StorageNode::new(self, 0)
+    }
+
+    /// Gets the node at the specified path of child offsets from the root, `None` if there is no
+    /// such node.
+    pub fn node_at_path(&self, path: &[usize]) -> Option<StorageNode<'_, N, Self>> {
+        StorageNode::new(self, self.index_calculator.path_index(path)?)
+    }
+
+    /// Sets the value of the node at the specified path of child offsets from the root, returning
+    /// the previous value if there was one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if the path or the node's children cannot be addressed and
+    /// `PathError::MissingParent` if the node's parent does not exist.
+    pub fn insert(&mut self, path: &[usize], value: N) -> Result<Option<N>, PathError> {
+        let index = match self.index_calculator.path_index(path).filter(|&index| {
+            let last_child_offset = self.index_calculator.child_count(index) - 1;
+            self.index_calculator
+                .try_child_index(index, last_child_offset)
+                .is_ok()
+        }) {
+            Some(index) => index,
+            None => {
+                return Err(PathError::InvalidPath {
+                    path: path.to_vec(),
+                })
+            }
+        };
+
+        if let Some(parent_index) = self.index_calculator.parent_index(index) {
+            if !self.nodes.contains_key(&parent_index) {
+                return Err(PathError::MissingParent {
+                    path: path.to_vec(),
+                });
+            }
+        }
+
+        Ok(self.nodes.insert(index, value))
+    }
+
+    /// Removes the node at the specified path of child offsets from the root along with all of
+    /// its descendants, returning its value.
+    pub fn remove(&mut self, path: &[usize]) -> Option<N> {
+        let index = self.index_calculator.path_index(path)?;
+        self.remove_index(index)
+    }
+
+    /// Gets an iterator over the path of child offsets from the root and the value of every node,
+    /// in breadth-first order.
+    pub fn iter_paths(&self) -> impl Iterator<Item = (Vec<usize>, &N)> {
+        let mut indexes: Vec<_> = self.nodes.keys().copied().collect();
+        indexes.sort_unstable();
+
+        indexes
+            .into_iter()
+            .map(move |index| (self.index_calculator.path(index), &self.nodes[&index]))
+    }
+
+    /// Converts the tree into a depth-first iterator of owned values.
+    pub fn into_depth_first_iterator(self, order: DepthFirstOrder) -> DepthFirstIterator<N, Self> {
+        DepthFirstIterator::new(self, order)
+    }
+
+    /// Converts the tree into a breadth-first iterator of owned values.
+    pub fn into_breadth_first_iterator(self) -> BreadthFirstIterator<N, Self> {
+        BreadthFirstIterator::new(self)
+    }
+
+    /// Removes the node at the specified index along with all of its descendants.
+    fn remove_index(&mut self, index: usize) -> Option<N> {
+        let value = self.nodes.remove(&index)?;
+
+        let mut pending = vec![index];
+        while let Some(index) = pending.pop() {
+            for child_index in self.index_calculator.child_indexes(index) {
+                if self.nodes.remove(&child_index).is_some() {
+                    pending.push(child_index);
+                }
+            }
+        }
+
+        Some(value)
+    }
+}
+
+impl<N> TreeStorage<N> for SparseEytzingerTree<N> {
+    fn max_children_per_node(&self) -> usize {
+        SparseEytzingerTree::max_children_per_node(self)
+    }
+
+    fn len(&self) -> usize {
+        SparseEytzingerTree::len(self)
+    }
+
+    fn value(&self, index: usize) -> Option<&N> {
+        self.nodes.get(&index)
+    }
+
+    fn value_mut(&mut self, index: usize) -> Option<&mut N> {
+        self.nodes.get_mut(&index)
+    }
+
+    fn set(&mut self, index: usize, value: Option<N>) -> Option<N> {
+        match value {
+            Some(value) => {
+                if let Some(parent_index) = self.index_calculator.parent_index(index) {
+                    assert!(
+                        self.nodes.contains_key(&parent_index),
+                        "the parent of the node at index {} should exist",
+                        index
+                    );
+                }
+
+                self.nodes.insert(index, value)
+            }
+            None => self.remove_index(index),
+        }
+    }
+
+    fn parent_index(&self, child_index: usize) -> Option<usize> {
+        self.index_calculator.parent_index(child_index)
+    }
+
+    fn child_index(&self, parent_index: usize, child_offset: usize) -> usize {
+        self.index_calculator
+            .child_index(parent_index, child_offset)
+    }
+
+    fn child_indexes(&self, parent_index: usize) -> Range<usize> {
+        self.index_calculator.child_indexes(parent_index)
+    }
+}
+
+impl<N> sealed::TakeValue<N> for SparseEytzingerTree<N> {
+    fn take_value(&mut self, index: usize, _: sealed::Token) -> Option<N> {
+        self.nodes.remove(&index)
+    }
+}
+
+impl<N> From<EytzingerTree<N>> for SparseEytzingerTree<N> {
+    fn from(tree: EytzingerTree<N>) -> Self {
+        Self {
+            nodes: tree
+                .nodes
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, value)| value.map(|value| (index, value)))
+                .collect(),
+            index_calculator: tree.index_calculator,
+        }
+    }
+}
+
+impl<N> From<SparseEytzingerTree<N>> for EytzingerTree<N> {
+    /// Converts a sparse tree into a dense one, this allocates a slot for every position up to
+    /// the deepest, right-most node.
+    fn from(tree: SparseEytzingerTree<N>) -> Self {
+        let mut dense = EytzingerTree::with_index_calculator(tree.index_calculator);
+
+        let mut nodes: Vec<_> = tree.nodes.into_iter().collect();
+        nodes.sort_unstable_by_key(|&(index, _)| index);
+
+        for (index, value) in nodes {
+            dense.set_value(index, value);
+        }
+
+        dense
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::PathError, test_util::sample_tree, DepthFirstOrder, EytzingerTree,
+        SparseEytzingerTree,
+    };
+
+    #[test]
+    fn round_trips_through_dense() {
+        let tree = sample_tree();
+
+        let sparse = SparseEytzingerTree::from(tree.clone());
+        assert_eq!(sparse.len(), 7);
+        assert_eq!(sparse.get(&[0, 1, 0]), Some(&3));
+        assert!(sparse.iter_paths().eq(tree.iter_paths()));
+
+        assert_eq!(EytzingerTree::from(sparse), tree);
+    }
+
+    #[test]
+    fn deep_chain_uses_memory_proportional_to_depth() {
+        // a binary chain this deep would require 2^60 slots in a dense tree
+        let depth = 60;
+        let mut tree = SparseEytzingerTree::new(2);
+
+        let mut path = vec![];
+        for value in 0..depth {
+            assert_eq!(tree.insert(&path, value), Ok(None));
+            path.push(1);
+        }
+
+        assert_eq!(tree.len(), depth);
+        assert!(tree.nodes.capacity() < depth * 2);
+        assert_eq!(tree.get(&[1; 59]), Some(&59));
+    }
+
+    #[test]
+    fn owned_iterators_match_dense() {
+        let tree = sample_tree();
+        let sparse = SparseEytzingerTree::from(tree.clone());
+
+        assert!(sparse
+            .clone()
+            .into_breadth_first_iterator()
+            .eq(tree.clone().into_breadth_first_iterator()));
+
+        for &order in &[DepthFirstOrder::PreOrder, DepthFirstOrder::PostOrder] {
+            assert!(sparse
+                .clone()
+                .into_depth_first_iterator(order)
+                .eq(tree.clone().into_depth_first_iterator(order)));
+        }
+    }
+
+    #[test]
+    fn insert_errors() {
+        let mut tree = SparseEytzingerTree::new(2);
+
+        assert_eq!(
+            tree.insert(&[0], 1),
+            Err(PathError::MissingParent { path: vec![0] })
+        );
+        assert_eq!(tree.insert(&[], 1), Ok(None));
+        assert_eq!(
+            tree.insert(&[2], 1),
+            Err(PathError::InvalidPath { path: vec![2] })
+        );
+        assert_eq!(
+            tree.insert(&[1; 64], 1),
+            Err(PathError::InvalidPath { path: vec![1; 64] })
+        );
+    }
+
+    #[test]
+    fn remove_removes_descendants() {
+        let mut tree = SparseEytzingerTree::from(sample_tree());
+
+        assert_eq!(tree.remove(&[0]), Some(2));
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.get(&[0, 1, 0]), None);
+        assert_eq!(tree.get(&[1, 1]), Some(&8));
+        assert_eq!(tree.remove(&[0]), None);
+    }
+}
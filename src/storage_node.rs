@@ -0,0 +1,154 @@
+use crate::TreeStorage;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Represents a borrowed node of any `TreeStorage`, such as an `EytzingerTree` or a
+/// `SparseEytzingerTree`. This node may be used to navigate to parent or child nodes.
+#[derive(Debug)]
+pub struct StorageNode<'a, N, S>
+where
+    S: ?Sized,
+{
+    storage: &'a S,
+    index: usize,
+    _value: PhantomData<&'a N>,
+}
+
+impl<'a, N, S> Copy for StorageNode<'a, N, S> where S: ?Sized {}
+
+impl<'a, N, S> Clone for StorageNode<'a, N, S>
+where
+    S: ?Sized,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, N, S> StorageNode<'a, N, S>
+where
+    S: TreeStorage<N> + ?Sized,
+{
+    /// Gets the node at the specified index of the storage, `None` if there is no such node.
+    pub(crate) fn new(storage: &'a S, index: usize) -> Option<Self> {
+        storage.value(index).map(|_| Self {
+            storage,
+            index,
+            _value: PhantomData,
+        })
+    }
+
+    /// Gets the storage this node is for.
+    pub fn storage(&self) -> &'a S {
+        self.storage
+    }
+
+    /// Gets the flat storage index of this node.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets the value stored at this node.
+    pub fn value(&self) -> &'a N {
+        self.storage
+            .value(self.index)
+            .expect("there should be a value at the node's index")
+    }
+
+    /// Gets the parent of this node or `None` if this is the root.
+    pub fn parent(&self) -> Option<Self> {
+        let parent_index = self.storage.parent_index(self.index)?;
+        Self::new(self.storage, parent_index)
+    }
+
+    /// Gets the child of this node at the specified index or `None` if there wasn't one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than `max_children_per_node`.
+    pub fn child(&self, index: usize) -> Option<Self> {
+        Self::new(self.storage, self.storage.child_index(self.index, index))
+    }
+
+    /// Gets an iterator over the immediate children of this node along with the child index each
+    /// was found at. This only includes children for which there is a node.
+    pub fn children_indexed(&self) -> impl Iterator<Item = (usize, Self)> + 'a {
+        let storage = self.storage;
+        storage
+            .child_indexes(self.index)
+            .enumerate()
+            .filter_map(move |(child, child_index)| {
+                Self::new(storage, child_index).map(|node| (child, node))
+            })
+    }
+
+    /// Gets the path of child indexes from the root to this node.
+    pub fn path(&self) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut current = self.index;
+
+        while let Some(parent_index) = self.storage.parent_index(current) {
+            path.push(current - self.storage.child_index(parent_index, 0));
+            current = parent_index;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Gets whether this node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.children_indexed().next().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::sample_tree, EytzingerTree, TreeStorage};
+
+    #[test]
+    fn navigates_dense() {
+        let tree = sample_tree();
+
+        let node = tree.storage_root().unwrap().child(0).unwrap();
+        assert_eq!(node.value(), &2);
+        assert_eq!(node.parent().map(|n| *n.value()), Some(5));
+
+        let children: Vec<_> = node
+            .children_indexed()
+            .map(|(child, node)| (child, *node.value()))
+            .collect();
+        assert_eq!(children, vec![(0, 1), (1, 4)]);
+
+        let leaf = node.child(1).unwrap().child(0).unwrap();
+        assert_eq!(leaf.path(), vec![0, 1, 0]);
+        assert!(leaf.is_leaf());
+        assert!(EytzingerTree::<u32>::new(2).storage_root().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn navigates_sparse_like_dense() {
+        use crate::SparseEytzingerTree;
+
+        let dense = sample_tree();
+        let sparse = SparseEytzingerTree::from(dense.clone());
+
+        let dense_node = dense.storage_root().unwrap().child(0).unwrap();
+        let sparse_node = sparse.root().unwrap().child(0).unwrap();
+        assert_eq!(sparse_node.value(), dense_node.value());
+        assert_eq!(sparse_node.parent().map(|n| *n.value()), Some(5));
+        assert!(sparse_node
+            .children_indexed()
+            .map(|(child, node)| (child, *node.value()))
+            .eq(dense_node
+                .children_indexed()
+                .map(|(child, node)| (child, *node.value()))));
+
+        let leaf = sparse.node_at_path(&[0, 1, 0]).unwrap();
+        assert_eq!(leaf.value(), &3);
+        assert_eq!(leaf.path(), vec![0, 1, 0]);
+        assert!(leaf.is_leaf());
+        assert!(sparse.node_at_path(&[1, 0]).is_none());
+    }
+}
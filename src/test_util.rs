@@ -0,0 +1,54 @@
+use crate::{entry::Entry, EytzingerTree};
+
+/// Builds the binary tree used throughout the tests:
+///
+/// ```text
+///         5
+///       /   \
+///      2     7
+///     / \     \
+///    1   4     8
+///       /
+///      3
+/// ```
+pub(crate) fn sample_tree() -> EytzingerTree<u32> {
+    let mut tree = EytzingerTree::<u32>::new(2);
+    {
+        let mut root = tree.set_root_value(5);
+        {
+            let mut left = root.set_child_value(0, 2);
+
+            left.set_child_value(0, 1);
+            let mut left_right = left.set_child_value(1, 4);
+            left_right.set_child_value(0, 3);
+        }
+        {
+            let mut right = root.set_child_value(1, 7);
+            right.set_child_value(1, 8);
+        }
+    }
+    tree
+}
+
+/// Builds a binary search tree by inserting each value in turn.
+pub(crate) fn bst_tree(values: &[u32]) -> EytzingerTree<u32> {
+    let mut tree = EytzingerTree::<u32>::new(2);
+
+    for &value in values {
+        let mut entry = tree.root_entry();
+        loop {
+            match entry {
+                Entry::Occupied(node) => {
+                    let child = if value < *node.value() { 0 } else { 1 };
+                    entry = node.to_child_entry(child);
+                }
+                Entry::Vacant(vacant) => {
+                    vacant.insert(value);
+                    break;
+                }
+            }
+        }
+    }
+
+    tree
+}
@@ -4,6 +4,9 @@ pub use self::node_child_iter::NodeChildIter;
 mod traversal_root;
 pub(crate) use self::traversal_root::TraversalRoot;
 
+mod traversal_start;
+pub use self::traversal_start::TraversalStart;
+
 mod breadth_first_iter;
 pub use self::breadth_first_iter::BreadthFirstIter;
 
@@ -18,3 +21,9 @@ pub use self::depth_first_iter::DepthFirstIter;
 
 mod depth_first_iterator;
 pub use self::depth_first_iterator::DepthFirstIterator;
+
+mod tree_event;
+pub use self::tree_event::TreeEvent;
+
+mod tree_visitor;
+pub use self::tree_visitor::TreeVisitor;
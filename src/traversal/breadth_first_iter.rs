@@ -1,9 +1,9 @@
 use crate::{
-    traversal::{NodeChildIter, TraversalRoot},
+    traversal::{NodeChildIter, TraversalRoot, TraversalStart},
     EytzingerTree, Node,
 };
-use std::collections::VecDeque;
-use std::iter::FusedIterator;
+use alloc::collections::VecDeque;
+use core::iter::FusedIterator;
 
 /// A breadth-first iterator.
 #[derive(Debug)]
@@ -38,6 +38,15 @@ impl<'a, N> BreadthFirstIter<'a, N> {
         Self { root, nodes }
     }
 
+    /// Creates a breadth-first iterator over the nodes from the specified start.
+    pub fn from_start<S>(start: S) -> Self
+    where
+        S: Into<TraversalStart<'a, N>>,
+    {
+        let start = start.into();
+        Self::new(start.tree(), start.node())
+    }
+
     /// Gets the starting/root node of this iterator or `None` if there was not one. There will be
     /// no starting node for an empty Eytzinger tree.
     pub fn starting_node(&self) -> Option<Node<'a, N>> {
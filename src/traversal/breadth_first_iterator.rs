@@ -1,53 +1,103 @@
-use crate::EytzingerTree;
-use std::collections::VecDeque;
-use std::iter::{ExactSizeIterator, FusedIterator};
-use std::ops::Range;
+use crate::{tree_storage::sealed::Token, EytzingerTree, Node, TreeStorage};
+use alloc::collections::VecDeque;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
 
 /// A breadth-first iterator which returns owned values.
 #[derive(Debug, Clone)]
-pub struct BreadthFirstIterator<N> {
-    tree: EytzingerTree<N>,
+pub struct BreadthFirstIterator<N, S = EytzingerTree<N>> {
+    tree: S,
     pending_nodes: VecDeque<Range<usize>>,
+    peeked: Option<usize>,
+    marker: PhantomData<N>,
 }
 
 impl<N> BreadthFirstIterator<N> {
-    pub(crate) fn new(tree: EytzingerTree<N>) -> Self {
+    /// Gets the next node without advancing the iterator, `None` if there are no more nodes.
+    pub fn peek(&mut self) -> Option<Node<'_, N>> {
+        if self.peeked.is_none() {
+            self.peeked = self.advance();
+        }
+
+        let index = self.peeked?;
+        self.tree.node(index)
+    }
+}
+
+impl<N, S> BreadthFirstIterator<N, S>
+where
+    S: TreeStorage<N>,
+{
+    pub(crate) fn new(tree: S) -> Self {
         let mut pending_nodes = VecDeque::new();
 
-        if tree.root().is_some() {
+        if tree.value(0).is_some() {
             pending_nodes.push_back(0..1);
         }
         Self {
             tree,
             pending_nodes,
+            peeked: None,
+            marker: PhantomData,
         }
     }
-}
 
-impl<N> Iterator for BreadthFirstIterator<N> {
-    type Item = N;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances to the index of the next node to return. The value at the index is not taken.
+    fn advance(&mut self) -> Option<usize> {
         while let Some(mut current) = self.pending_nodes.pop_front() {
             if let Some(next) = current.next() {
-                if current.len() > 0 {
+                if !current.is_empty() {
                     // If there are still more remaining nodes at this level put them to the front
                     // of the queue
                     self.pending_nodes.push_front(current);
                 }
 
-                if let Some(next_value) = self.tree.value_mut(next).and_then(|v| v.take()) {
+                if self.tree.value(next).is_some() {
                     self.pending_nodes.push_back(self.tree.child_indexes(next));
-                    return Some(next_value);
+                    return Some(next);
                 }
             }
         }
         None
     }
+}
+
+impl<N, S> Iterator for BreadthFirstIterator<N, S>
+where
+    S: TreeStorage<N>,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.peeked.take().or_else(|| self.advance())?;
+
+        self.tree.take_value(index, Token(()))
+    }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         (0, Some(self.tree.len()))
     }
 }
 
-impl<N> FusedIterator for BreadthFirstIterator<N> {}
+impl<N, S> FusedIterator for BreadthFirstIterator<N, S> where S: TreeStorage<N> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::sample_tree;
+
+    #[test]
+    fn peek_returns_next() {
+        let mut iterator = sample_tree().into_breadth_first_iterator();
+
+        let mut values = vec![];
+        while let Some(peeked) = iterator.peek().map(|n| *n.value()) {
+            assert_eq!(iterator.peek().map(|n| *n.value()), Some(peeked));
+            assert_eq!(iterator.next(), Some(peeked));
+            values.push(peeked);
+        }
+
+        assert_eq!(iterator.next(), None);
+        assert_eq!(values, vec![5, 2, 7, 1, 4, 8, 3]);
+    }
+}
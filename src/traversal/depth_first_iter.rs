@@ -1,9 +1,10 @@
 use crate::{
-    traversal::{DepthFirstOrder, NodeChildIter, TraversalRoot},
+    traversal::{DepthFirstOrder, NodeChildIter, TraversalRoot, TraversalStart},
     EytzingerTree, Node,
 };
+use alloc::{vec, vec::Vec};
+use core::iter::FusedIterator;
 use matches::matches;
-use std::iter::FusedIterator;
 
 /// A depth-first iterator
 #[derive(Debug)]
@@ -48,6 +49,15 @@ impl<'a, N> DepthFirstIter<'a, N> {
         }
     }
 
+    /// Creates a depth-first iterator over the nodes from the specified start.
+    pub fn from_start<S>(start: S, order: DepthFirstOrder) -> Self
+    where
+        S: Into<TraversalStart<'a, N>>,
+    {
+        let start = start.into();
+        Self::new(start.tree(), start.node(), order)
+    }
+
     /// Gets the order of depth-first iteration.
     pub fn order(&self) -> DepthFirstOrder {
         self.order
@@ -1,20 +1,40 @@
-use crate::{DepthFirstOrder, EytzingerTree};
+use crate::{tree_storage::sealed::Token, DepthFirstOrder, EytzingerTree, Node, TreeStorage};
+use core::marker::PhantomData;
 use matches::matches;
 
 /// A depth-first iterator which returns owned values.
 #[derive(Debug, Clone)]
-pub struct DepthFirstIterator<N> {
+pub struct DepthFirstIterator<N, S = EytzingerTree<N>> {
     order: DepthFirstOrder,
-    tree: EytzingerTree<N>,
+    tree: S,
     index: usize,
+    peeked: Option<usize>,
+    marker: PhantomData<N>,
 }
 
 impl<N> DepthFirstIterator<N> {
-    pub(crate) fn new(tree: EytzingerTree<N>, order: DepthFirstOrder) -> Self {
+    /// Gets the next node without advancing the iterator, `None` if there are no more nodes.
+    pub fn peek(&mut self) -> Option<Node<'_, N>> {
+        if self.peeked.is_none() {
+            self.peeked = self.advance();
+        }
+
+        let index = self.peeked?;
+        self.tree.node(index)
+    }
+}
+
+impl<N, S> DepthFirstIterator<N, S>
+where
+    S: TreeStorage<N>,
+{
+    pub(crate) fn new(tree: S, order: DepthFirstOrder) -> Self {
         Self {
             order,
             tree,
             index: 0,
+            peeked: None,
+            marker: PhantomData,
         }
     }
 
@@ -22,52 +42,80 @@ impl<N> DepthFirstIterator<N> {
     pub fn order(&self) -> DepthFirstOrder {
         self.order
     }
-}
-
-impl<N> Iterator for DepthFirstIterator<N> {
-    type Item = N;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances to the index of the next node to return. The value at the index is not taken so
+    /// it must be taken before advancing again.
+    fn advance(&mut self) -> Option<usize> {
         loop {
-            if self
-                .tree
-                .value(self.index)
-                .and_then(|v| v.as_ref())
-                .is_some()
-            {
+            if self.tree.value(self.index).is_some() {
                 let current_index = self.index;
                 self.index = self.tree.child_index(current_index, 0);
                 if matches!(self.order, DepthFirstOrder::PreOrder) {
-                    let value = self
-                        .tree
-                        .value_mut(current_index)
-                        .and_then(|v| v.take())
-                        .expect("the value should not have been taken already");
-                    return Some(value);
+                    return Some(current_index);
                 }
-            } else {
-                if let Some(parent_index) = self.tree.parent_index(self.index) {
-                    let node_child_offset = self.index - self.tree.child_index(parent_index, 0);
-                    let next_child_offset = node_child_offset + 1;
-                    if next_child_offset < self.tree.max_children_per_node() {
-                        // try the next sibling
-                        self.index = self.tree.child_index(parent_index, next_child_offset);
-                    } else {
-                        self.index = parent_index;
-
-                        let removed_value = self.tree.remove(parent_index);
-                        if matches!(self.order, DepthFirstOrder::PostOrder) {
-                            return Some(
-                                removed_value
-                                    .expect("the value should not have been taken already"),
-                            );
-                        }
-                    }
+            } else if let Some(parent_index) = self.tree.parent_index(self.index) {
+                let node_child_offset = self.index - self.tree.child_index(parent_index, 0);
+                let next_child_offset = node_child_offset + 1;
+                if next_child_offset < self.tree.child_indexes(parent_index).len() {
+                    // try the next sibling
+                    self.index = self.tree.child_index(parent_index, next_child_offset);
                 } else {
-                    // we have returned back to the root
-                    return None;
+                    // all children have been taken, for pre-order the parent has already been
+                    // returned so its slot will already be vacant
+                    self.index = parent_index;
+                    if matches!(self.order, DepthFirstOrder::PostOrder) {
+                        return Some(parent_index);
+                    }
                 }
+            } else {
+                // we have returned back to the root
+                return None;
             }
         }
     }
 }
+
+impl<N, S> Iterator for DepthFirstIterator<N, S>
+where
+    S: TreeStorage<N>,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.peeked.take().or_else(|| self.advance())?;
+
+        let value = self
+            .tree
+            .take_value(index, Token(()))
+            .expect("the value should not have been taken already");
+
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::sample_tree, DepthFirstOrder};
+
+    #[test]
+    fn peek_returns_next() {
+        for &order in &[DepthFirstOrder::PreOrder, DepthFirstOrder::PostOrder] {
+            let mut iterator = sample_tree().into_depth_first_iterator(order);
+
+            let mut values = vec![];
+            while let Some(peeked) = iterator.peek().map(|n| *n.value()) {
+                assert_eq!(iterator.peek().map(|n| *n.value()), Some(peeked));
+                assert_eq!(iterator.next(), Some(peeked));
+                values.push(peeked);
+            }
+
+            assert_eq!(iterator.next(), None);
+
+            let expected: Vec<_> = sample_tree()
+                .depth_first_iter(order)
+                .map(|n| *n.value())
+                .collect();
+            assert_eq!(values, expected);
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::Node;
-use std::iter::FusedIterator;
+use core::iter::FusedIterator;
 
 /// An iterator over the immediate children of a single node.
 #[derive(Debug)]
@@ -38,7 +38,7 @@ impl<'a, N> Iterator for NodeChildIter<'a, N> {
     type Item = Node<'a, N>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.child_offset < self.node.tree().max_children_per_node() {
+        while self.child_offset < self.node.tree().child_count(self.node.index()) {
             let next_child = self.node.child(self.child_offset);
             self.child_offset += 1;
             if let Some(next_child) = next_child {
@@ -49,7 +49,7 @@ impl<'a, N> Iterator for NodeChildIter<'a, N> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.node.tree().max_children_per_node()))
+        (0, Some(self.node.tree().child_count(self.node.index())))
     }
 }
 
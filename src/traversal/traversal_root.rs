@@ -27,10 +27,7 @@ impl<'a, N> TraversalRoot<'a, N> {
 
 impl<'a, N> Clone for TraversalRoot<'a, N> {
     fn clone(&self) -> Self {
-        match self {
-            TraversalRoot::Tree(tree) => TraversalRoot::Tree(tree),
-            TraversalRoot::Node(node) => TraversalRoot::Node(*node),
-        }
+        *self
     }
 }
 
@@ -0,0 +1,87 @@
+use crate::{EytzingerTree, Node};
+
+/// Where a traversal over a borrowed tree starts, this may be used to create a `DepthFirstIter` or
+/// `BreadthFirstIter` over any subtree.
+#[derive(Debug)]
+pub enum TraversalStart<'a, N>
+where
+    N: 'a,
+{
+    /// The traversal starts at the root of the tree. There are no nodes to traverse if the tree is
+    /// empty.
+    Root(&'a EytzingerTree<N>),
+    /// The traversal starts at the node and only includes the node and its descendants.
+    Node(Node<'a, N>),
+}
+
+impl<'a, N> TraversalStart<'a, N> {
+    /// Gets the tree the traversal is for.
+    pub fn tree(&self) -> &'a EytzingerTree<N> {
+        match self {
+            TraversalStart::Root(tree) => tree,
+            TraversalStart::Node(node) => node.tree(),
+        }
+    }
+
+    /// Gets the node the traversal starts at or `None` if it starts at the root of an empty tree.
+    pub fn node(&self) -> Option<Node<'a, N>> {
+        match self {
+            TraversalStart::Root(tree) => tree.root(),
+            TraversalStart::Node(node) => Some(*node),
+        }
+    }
+}
+
+impl<'a, N> Clone for TraversalStart<'a, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, N> Copy for TraversalStart<'a, N> {}
+
+impl<'a, N> From<&'a EytzingerTree<N>> for TraversalStart<'a, N> {
+    fn from(tree: &'a EytzingerTree<N>) -> Self {
+        TraversalStart::Root(tree)
+    }
+}
+
+impl<'a, N> From<Node<'a, N>> for TraversalStart<'a, N> {
+    fn from(node: Node<'a, N>) -> Self {
+        TraversalStart::Node(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TraversalStart;
+    use crate::{
+        test_util::sample_tree,
+        traversal::{BreadthFirstIter, DepthFirstIter, DepthFirstOrder},
+        EytzingerTree,
+    };
+
+    #[test]
+    fn depth_first_iter_from_node() {
+        let tree = sample_tree();
+        let right = tree.root().unwrap().child(1).unwrap();
+
+        let values: Vec<_> = DepthFirstIter::from_start(right, DepthFirstOrder::PreOrder)
+            .map(|n| *n.value())
+            .collect();
+
+        assert_eq!(values, vec![7, 8]);
+    }
+
+    #[test]
+    fn breadth_first_iter_from_root() {
+        let tree = sample_tree();
+
+        assert!(BreadthFirstIter::from_start(&tree).eq(tree.breadth_first_iter()));
+
+        let empty = EytzingerTree::<u32>::new(2);
+        let start = TraversalStart::Root(&empty);
+        assert!(start.node().is_none());
+        assert_eq!(BreadthFirstIter::from_start(start).count(), 0);
+    }
+}
@@ -0,0 +1,31 @@
+use crate::Node;
+
+/// An event of a depth-first walk over a tree. Each node produces an `Enter` event before the
+/// events of its children and a `Leave` event after them.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum TreeEvent<'a, N>
+where
+    N: 'a,
+{
+    /// The walk has reached the node, the events of its children follow.
+    Enter(Node<'a, N>),
+    /// The walk has finished with the node and all of its children.
+    Leave(Node<'a, N>),
+}
+
+impl<'a, N> Copy for TreeEvent<'a, N> {}
+
+impl<'a, N> Clone for TreeEvent<'a, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, N> TreeEvent<'a, N> {
+    /// Gets the node this event is for.
+    pub fn node(&self) -> Node<'a, N> {
+        match *self {
+            TreeEvent::Enter(node) | TreeEvent::Leave(node) => node,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::Node;
+
+/// A visitor of a depth-first walk over a tree, see `EytzingerTree::visit`. This is the
+/// callback counterpart of the events returned by `EytzingerTree::events_iter`.
+pub trait TreeVisitor<N> {
+    /// Called when the walk reaches a node, before any of its children are visited.
+    fn enter(&mut self, node: &Node<'_, N>);
+
+    /// Called when the walk has finished with a node and all of its children.
+    fn leave(&mut self, node: &Node<'_, N>);
+}
@@ -0,0 +1,183 @@
+use crate::{EytzingerIndexCalculator, EytzingerTree, StorageNode};
+use core::ops::Range;
+
+/// Storage for the values of a tree using the Eytzinger layout, each node is identified by its
+/// index. This allows algorithms such as the owning traversals to work over both the dense
+/// `EytzingerTree` and the `SparseEytzingerTree`.
+///
+/// This trait is sealed so it cannot be implemented outside of this crate.
+pub trait TreeStorage<N>: sealed::TakeValue<N> {
+    /// Gets the maximum number of children per node.
+    fn max_children_per_node(&self) -> usize;
+
+    /// Gets the number of nodes in the storage.
+    fn len(&self) -> usize;
+
+    /// Gets whether the storage has no nodes.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the value of the node at the specified index, `None` if there is no such node.
+    fn value(&self, index: usize) -> Option<&N>;
+
+    /// Gets the mutable value of the node at the specified index, `None` if there is no such node.
+    fn value_mut(&mut self, index: usize) -> Option<&mut N>;
+
+    /// Sets or clears the value of the node at the specified index, returning the previous value.
+    /// Setting a value keeps the node's descendants, clearing a value removes the node along with
+    /// all of its descendants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value is set for a node whose parent does not exist.
+    fn set(&mut self, index: usize, value: Option<N>) -> Option<N>;
+
+    /// Gets the index of the parent of the node at the specified index, `None` for the root.
+    fn parent_index(&self, child_index: usize) -> Option<usize> {
+        EytzingerIndexCalculator::new(self.max_children_per_node()).parent_index(child_index)
+    }
+
+    /// Gets the index of the child at the specified offset of the node at the specified index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child_offset` is not less than `max_children_per_node`.
+    fn child_index(&self, parent_index: usize, child_offset: usize) -> usize {
+        EytzingerIndexCalculator::new(self.max_children_per_node())
+            .child_index(parent_index, child_offset)
+    }
+
+    /// Gets the range of indexes of all children of the node at the specified index.
+    fn child_indexes(&self, parent_index: usize) -> Range<usize> {
+        EytzingerIndexCalculator::new(self.max_children_per_node()).child_indexes(parent_index)
+    }
+
+    /// Gets the root node of the storage, `None` if the storage is empty. The node may be used to
+    /// navigate the same way for every kind of storage.
+    fn storage_root(&self) -> Option<StorageNode<'_, N, Self>> {
+        StorageNode::new(self, 0)
+    }
+}
+
+impl<N> TreeStorage<N> for EytzingerTree<N> {
+    fn max_children_per_node(&self) -> usize {
+        EytzingerTree::max_children_per_node(self)
+    }
+
+    fn len(&self) -> usize {
+        EytzingerTree::len(self)
+    }
+
+    fn value(&self, index: usize) -> Option<&N> {
+        self.nodes.get(index).and_then(Option::as_ref)
+    }
+
+    fn value_mut(&mut self, index: usize) -> Option<&mut N> {
+        self.nodes.get_mut(index).and_then(Option::as_mut)
+    }
+
+    fn set(&mut self, index: usize, value: Option<N>) -> Option<N> {
+        match value {
+            Some(value) => {
+                if let Some(parent_index) = EytzingerTree::parent_index(self, index) {
+                    assert!(
+                        self.occupancy.contains(parent_index),
+                        "the parent of the node at index {} should exist",
+                        index
+                    );
+                }
+
+                let old_value = sealed::TakeValue::take_value(self, index, sealed::Token(()));
+                self.set_value(index, value);
+                old_value
+            }
+            None => self.remove(index),
+        }
+    }
+
+    fn parent_index(&self, child_index: usize) -> Option<usize> {
+        self.index_calculator.parent_index(child_index)
+    }
+
+    fn child_index(&self, parent_index: usize, child_offset: usize) -> usize {
+        self.index_calculator
+            .child_index(parent_index, child_offset)
+    }
+
+    fn child_indexes(&self, parent_index: usize) -> Range<usize> {
+        self.index_calculator.child_indexes(parent_index)
+    }
+}
+
+impl<N> sealed::TakeValue<N> for EytzingerTree<N> {
+    fn take_value(&mut self, index: usize, _: sealed::Token) -> Option<N> {
+        let old_value = EytzingerTree::take_value(self, index);
+        if old_value.is_some() {
+            self.len -= 1;
+        }
+        old_value
+    }
+}
+
+pub(crate) mod sealed {
+    /// Proof that a caller is within this crate, it cannot be constructed elsewhere.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Token(pub(crate) ());
+
+    /// Takes the value of the node at the specified index without affecting its descendants. This
+    /// may leave children under a vacant parent so it is only used by the owning traversals, which
+    /// always take a parent's children before the tree is observed again.
+    pub trait TakeValue<N> {
+        fn take_value(&mut self, index: usize, token: Token) -> Option<N>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::sample_tree, EytzingerTree, TreeStorage};
+
+    #[test]
+    fn set_and_clear() {
+        let mut tree = EytzingerTree::new(2);
+
+        assert_eq!(tree.set(0, Some(5)), None);
+        assert_eq!(tree.set(2, Some(7)), None);
+        assert_eq!(tree.set(2, Some(8)), Some(7));
+        assert_eq!(tree.value(2), Some(&8));
+        assert_eq!(tree.len(), 2);
+
+        assert_eq!(tree.set(2, None), Some(8));
+        assert_eq!(tree.value(2), None);
+        assert_eq!(tree.len(), 1);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn clear_removes_descendants() {
+        let mut tree = sample_tree();
+
+        assert_eq!(tree.set(1, None), Some(2));
+        assert_eq!(tree.value(9), None);
+        assert_eq!(tree.len(), 3);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    fn set_keeps_descendants() {
+        let mut tree = sample_tree();
+
+        assert_eq!(tree.set(1, Some(6)), Some(2));
+        assert_eq!(tree.value(9), Some(&3));
+        assert_eq!(tree.len(), 7);
+        assert!(tree.is_consistent());
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_without_parent_panics() {
+        let mut tree = sample_tree();
+
+        tree.set(11, Some(6));
+    }
+}